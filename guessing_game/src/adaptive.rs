@@ -0,0 +1,161 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome};
+use crate::i18n::message;
+use crate::input::LineSource;
+use crate::play::{direction, play_loop};
+use crate::timed::TimeLimits;
+
+/// Where every player starts.
+pub const INITIAL_RATING: f64 = 1200.0;
+/// The rating of a game that gives exactly as many attempts as bisection
+/// needs: only a player as good as bisection wins it half the time.
+pub const BASELINE_RATING: f64 = 2000.0;
+/// How much easier each attempt beyond bisection's makes a game.
+pub const SLACK_POINTS: f64 = 150.0;
+/// The most a rating moves after one game.
+pub const K_FACTOR: f64 = 32.0;
+pub const DEFAULT_TARGET: f64 = 0.7;
+
+/// Ranges to pick from, smallest first; better players get bigger ones.
+const RANGE_LADDER: [i64; 9] = [10, 50, 100, 500, 1_000, 5_000, 10_000, 100_000, 1_000_000];
+/// Rating points per step up the ladder.
+const LADDER_STEP: f64 = 150.0;
+
+/// One round's range and attempt limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge {
+    pub difficulty: Difficulty,
+    pub max_attempts: u32,
+}
+
+impl Challenge {
+    /// How hard the round is on the rating scale, judged by how many spare
+    /// attempts it allows over a perfect bisection.
+    pub fn rating(&self) -> f64 {
+        let slack = f64::from(self.max_attempts) - f64::from(self.difficulty.optimal_guesses());
+        BASELINE_RATING - SLACK_POINTS * slack
+    }
+}
+
+/// An Elo-style estimate of how well someone plays, with their record in
+/// adaptive rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Skill {
+    pub rating: f64,
+    pub games: u32,
+    pub wins: u32,
+}
+
+impl Default for Skill {
+    fn default() -> Self {
+        Self {
+            rating: INITIAL_RATING,
+            games: 0,
+            wins: 0,
+        }
+    }
+}
+
+impl Skill {
+    /// The chance this player wins `challenge`.
+    pub fn expected(&self, challenge: &Challenge) -> f64 {
+        1.0 / (1.0 + 10f64.powf((challenge.rating() - self.rating) / 400.0))
+    }
+
+    /// Moves the rating towards the result and returns the change. A game
+    /// the player quit does not count.
+    pub fn record(&mut self, challenge: &Challenge, outcome: Outcome) -> f64 {
+        let actual = match outcome {
+            Outcome::Won { .. } => 1.0,
+            Outcome::Lost { .. } => 0.0,
+            Outcome::Quit { .. } => return 0.0,
+        };
+        let change = K_FACTOR * (actual - self.expected(challenge));
+        self.rating += change;
+        self.games += 1;
+        if actual > 0.0 {
+            self.wins += 1;
+        }
+        change
+    }
+
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.games > 0).then(|| f64::from(self.wins) / f64::from(self.games))
+    }
+
+    /// A round this player should win with probability close to `target`:
+    /// the range grows with the rating, and the attempt limit is whatever
+    /// makes the odds come out right.
+    pub fn next_challenge(&self, target: f64) -> Challenge {
+        let target = target.clamp(0.01, 0.99);
+        let step = ((self.rating - INITIAL_RATING) / LADDER_STEP + 2.0).round();
+        let max = RANGE_LADDER[step.clamp(0.0, (RANGE_LADDER.len() - 1) as f64) as usize];
+        let difficulty = Difficulty::Custom { min: 1, max };
+
+        // Solve expected() == target for the challenge rating, then for the
+        // attempts that give it.
+        let wanted = self.rating + 400.0 * (1.0 / target - 1.0).log10();
+        let slack = (BASELINE_RATING - wanted) / SLACK_POINTS;
+        let attempts = f64::from(difficulty.optimal_guesses()) + slack;
+        Challenge {
+            difficulty,
+            max_attempts: attempts.round().max(1.0) as u32,
+        }
+    }
+}
+
+/// Plays one adaptive round: picks a challenge for `skill`, plays it, and
+/// reports the new rating.
+pub fn play_adaptive_round<R, I, O>(
+    rng: &mut R,
+    skill: &mut Skill,
+    target: f64,
+    input: &mut I,
+    mut output: O,
+) -> io::Result<(Challenge, Outcome)>
+where
+    R: Rng,
+    I: LineSource + ?Sized,
+    O: Write,
+{
+    let challenge = skill.next_challenge(target);
+    let mut game = Game::with_difficulty(&mut *rng, challenge.difficulty)
+        .with_max_attempts(Some(challenge.max_attempts));
+
+    let chance = format!("{:.0}", skill.expected(&challenge) * 100.0);
+    writeln!(
+        output,
+        "{}",
+        message(
+            "adaptive.start",
+            &[("rating", &skill.rating.round()), ("chance", &chance)]
+        )
+    )?;
+    let outcome = play_loop(
+        &mut game,
+        input,
+        &mut output,
+        direction,
+        &TimeLimits::default(),
+        true,
+    )?;
+
+    if !matches!(outcome, Outcome::Quit { .. }) {
+        let change = skill.record(&challenge, outcome);
+        let change = format!("{change:+.0}");
+        writeln!(
+            output,
+            "{}",
+            message(
+                "adaptive.rating",
+                &[("rating", &skill.rating.round()), ("change", &change)]
+            )
+        )?;
+    }
+    Ok((challenge, outcome))
+}
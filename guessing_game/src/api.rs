@@ -0,0 +1,415 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome};
+use crate::input::InputError;
+
+// Routes:
+//
+//   POST /games                {"difficulty"?, "min"?, "max"?, "seed"?, "attempts"?}
+//   POST /games/{id}/guesses   {"guess": n}
+//   GET  /games/{id}
+
+/// Body of `POST /games`. Every field is optional; `min`/`max` together
+/// pick a custom range instead of `difficulty`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NewGame {
+    difficulty: Option<String>,
+    min: Option<i64>,
+    max: Option<i64>,
+    seed: Option<u64>,
+    attempts: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGuess {
+    guess: i64,
+}
+
+struct Session {
+    game: Game<StdRng>,
+    outcome: Option<Outcome>,
+    last_used: Instant,
+}
+
+/// An HTTP response before it is written out: a status code and JSON body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn new(status: u16, body: Value) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::new(status, json!({ "error": message.into() }))
+    }
+}
+
+/// In-memory game sessions behind the JSON API. Sessions untouched for
+/// longer than the time-to-live are dropped.
+pub struct Api {
+    sessions: HashMap<u64, Session>,
+    next_id: u64,
+    ttl: Duration,
+}
+
+impl Api {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            sessions: HashMap::new(),
+            next_id: 1,
+            ttl,
+        }
+    }
+
+    /// Number of sessions that have not expired yet.
+    pub fn session_count(&mut self) -> usize {
+        self.expire();
+        self.sessions.len()
+    }
+
+    /// Routes one request. `path` excludes any query string.
+    pub fn handle(&mut self, method: &str, path: &str, body: &str) -> Response {
+        self.expire();
+
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            ("POST", ["games"]) => self.create(body),
+            ("POST", ["games", id, "guesses"]) => match id.parse() {
+                Ok(id) => self.guess(id, body),
+                Err(_) => Response::error(404, "no such game"),
+            },
+            ("GET", ["games", id]) => match id.parse() {
+                Ok(id) => self.show(id),
+                Err(_) => Response::error(404, "no such game"),
+            },
+            (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "guesses"]) => {
+                Response::error(405, "method not allowed")
+            }
+            _ => Response::error(404, "not found"),
+        }
+    }
+
+    fn expire(&mut self) {
+        let ttl = self.ttl;
+        self.sessions
+            .retain(|_, session| session.last_used.elapsed() < ttl);
+    }
+
+    fn create(&mut self, body: &str) -> Response {
+        let request: NewGame = if body.trim().is_empty() {
+            NewGame::default()
+        } else {
+            match serde_json::from_str(body) {
+                Ok(request) => request,
+                Err(err) => return Response::error(400, err.to_string()),
+            }
+        };
+
+        let difficulty = match (&request.difficulty, request.min, request.max) {
+            (None, None, None) => Ok(Difficulty::Normal),
+            (Some(name), None, None) => name.parse(),
+            (None, Some(min), Some(max)) => Difficulty::custom(min, max),
+            (Some(_), _, _) => Err("difficulty cannot be combined with min/max".to_string()),
+            _ => Err("min and max must be given together".to_string()),
+        };
+        let difficulty = match difficulty {
+            Ok(difficulty) => difficulty,
+            Err(message) => return Response::error(400, message),
+        };
+        if request.attempts == Some(0) {
+            return Response::error(400, "attempts must be at least 1");
+        }
+
+        let rng = match request.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let game = Game::with_difficulty(rng, difficulty).with_max_attempts(request.attempts);
+
+        let id = self.next_id;
+        self.next_id += 1;
+        self.sessions.insert(
+            id,
+            Session {
+                game,
+                outcome: None,
+                last_used: Instant::now(),
+            },
+        );
+        let session = &self.sessions[&id];
+        Response::new(201, describe(id, session))
+    }
+
+    fn guess(&mut self, id: u64, body: &str) -> Response {
+        let Some(session) = self.sessions.get_mut(&id) else {
+            return Response::error(404, "no such game");
+        };
+        session.last_used = Instant::now();
+
+        let request: NewGuess = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(err) => return Response::error(400, err.to_string()),
+        };
+        if session.outcome.is_some() {
+            return Response::error(409, "game is already over");
+        }
+        let range = session.game.range();
+        if !range.contains(&request.guess) {
+            let err = InputError::OutOfRange {
+                guess: request.guess.to_string(),
+                min: *range.start(),
+                max: *range.end(),
+            };
+            return Response::error(422, err.to_string());
+        }
+
+        let turn = session.game.take_turn(request.guess);
+        session.outcome = turn.outcome;
+
+        let mut body = json!({
+            "guess": request.guess,
+            "result": ordering_name(turn.ordering),
+            "attempts": session.game.attempts(),
+            "status": status(session.outcome),
+        });
+        if let Some(left) = session.game.attempts_left() {
+            body["attempts_left"] = json!(left);
+        }
+        if let Some(Outcome::Won { score, .. }) = session.outcome {
+            body["score"] = json!(score);
+        }
+        if session.outcome.is_some() {
+            body["secret"] = json!(session.game.secret_number());
+        }
+        Response::new(200, body)
+    }
+
+    fn show(&mut self, id: u64) -> Response {
+        let Some(session) = self.sessions.get_mut(&id) else {
+            return Response::error(404, "no such game");
+        };
+        session.last_used = Instant::now();
+
+        let mut body = describe(id, session);
+        body["history"] = session
+            .game
+            .history()
+            .iter()
+            .map(|&(guess, ordering)| json!({ "guess": guess, "result": ordering_name(ordering) }))
+            .collect();
+        if session.outcome.is_some() {
+            body["secret"] = json!(session.game.secret_number());
+        }
+        Response::new(200, body)
+    }
+}
+
+fn describe(id: u64, session: &Session) -> Value {
+    let game = &session.game;
+    let range = game.range();
+    json!({
+        "id": id,
+        "difficulty": game.difficulty().to_string(),
+        "min": range.start(),
+        "max": range.end(),
+        "attempts": game.attempts(),
+        "max_attempts": game.max_attempts(),
+        "status": status(session.outcome),
+    })
+}
+
+/// How the guess compared to the secret, as the CLI's `Ordering` names it.
+fn ordering_name(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "less",
+        Ordering::Greater => "greater",
+        Ordering::Equal => "equal",
+    }
+}
+
+fn status(outcome: Option<Outcome>) -> &'static str {
+    match outcome {
+        None => "playing",
+        Some(Outcome::Won { .. }) => "won",
+        Some(Outcome::Lost { .. }) => "lost",
+        Some(Outcome::Quit { .. }) => "quit",
+    }
+}
+
+/// Serves an [`Api`] over HTTP/1.1, one thread and one request per
+/// connection.
+pub struct HttpServer {
+    listener: TcpListener,
+    api: Arc<Mutex<Api>>,
+    timeout: Duration,
+}
+
+impl HttpServer {
+    pub fn bind<A: ToSocketAddrs>(addr: A, api: Api) -> io::Result<HttpServer> {
+        Ok(HttpServer {
+            listener: TcpListener::bind(addr)?,
+            api: Arc::new(Mutex::new(api)),
+            timeout: IO_TIMEOUT,
+        })
+    }
+
+    /// How long a connection may wait on the client before it is closed.
+    /// [`IO_TIMEOUT`] unless changed.
+    pub fn with_timeout(mut self, timeout: Duration) -> HttpServer {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let api = Arc::clone(&self.api);
+            let timeout = self.timeout;
+            thread::spawn(move || {
+                let _ = handle_connection(stream, &api, timeout);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// How long a connection waits on a read or write by default, so an idle
+/// or stalled client can't hold its thread forever.
+pub const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Requests larger than this are refused rather than buffered.
+const MAX_BODY: usize = 64 * 1024;
+
+/// The request line and headers together may be no larger than this.
+const MAX_HEAD: u64 = 8 * 1024;
+
+fn handle_connection(stream: TcpStream, api: &Mutex<Api>, timeout: Duration) -> io::Result<()> {
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut budget = MAX_HEAD;
+    let too_large = Response::error(431, "request head too large");
+
+    let Some(request_line) = read_head_line(&mut reader, &mut budget)? else {
+        return reject(&mut writer, &mut reader, &too_large);
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return reject(
+            &mut writer,
+            &mut reader,
+            &Response::error(400, "malformed request"),
+        );
+    };
+
+    let mut content_length = 0;
+    loop {
+        let Some(header) = read_head_line(&mut reader, &mut budget)? else {
+            return reject(&mut writer, &mut reader, &too_large);
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let Ok(length) = value.trim().parse() else {
+                    return reject(
+                        &mut writer,
+                        &mut reader,
+                        &Response::error(400, "invalid Content-Length"),
+                    );
+                };
+                content_length = length;
+            }
+        }
+    }
+    if content_length > MAX_BODY {
+        return reject(
+            &mut writer,
+            &mut reader,
+            &Response::error(413, "body too large"),
+        );
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let body = String::from_utf8_lossy(&body);
+    let path = target.split('?').next().unwrap_or(target);
+
+    let response = api.lock().unwrap().handle(method, path, &body);
+    write_response(&mut writer, &response)
+}
+
+/// Answers a request that was not read in full. The rest of it is read and
+/// dropped after the answer, since closing with unread data would reset the
+/// connection and could lose the answer before the client reads it.
+fn reject<R: Read>(writer: &mut TcpStream, reader: &mut R, response: &Response) -> io::Result<()> {
+    write_response(writer, response)?;
+    writer.shutdown(Shutdown::Write)?;
+    let _ = io::copy(
+        &mut reader.take(MAX_HEAD + MAX_BODY as u64),
+        &mut io::sink(),
+    );
+    Ok(())
+}
+
+/// Reads one line of the request head, taking its length off `budget`.
+/// `None` when the line doesn't fit in what is left.
+fn read_head_line<R: BufRead>(reader: &mut R, budget: &mut u64) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    let read = reader.take(*budget).read_line(&mut line)?;
+    *budget -= read as u64;
+    if *budget == 0 && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+fn write_response<W: Write>(writer: &mut W, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    write!(
+        writer,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        reason(response.status),
+        body.len()
+    )?;
+    writer.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        _ => "",
+    }
+}
@@ -0,0 +1,488 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::difficulty::Difficulty;
+use crate::game::Outcome;
+use crate::hotcold::Thresholds;
+use crate::i18n::Locale;
+use crate::timed::TimeLimits;
+use crate::tournament;
+use std::time::Duration;
+
+/// What the binary has been asked to do.
+#[derive(Debug, Default, PartialEq)]
+pub enum Command {
+    #[default]
+    Play,
+    /// The player picks the number and the program guesses it.
+    Reverse,
+    /// Break a secret code of `--length` digits, bulls-and-cows style.
+    Mastermind,
+    /// Play `--rounds` games back to back against the clock.
+    Speedrun,
+    /// Rounds sized to the `--player`'s rating, aiming at `--target` wins.
+    Adaptive,
+    /// Today's shared puzzle, once per day, with a result to share.
+    Daily,
+    /// Hot-seat rounds for the `--players` at this terminal.
+    Tournament,
+    /// Let every solver strategy play `--games` seeded rounds and compare.
+    Bench,
+    /// Host a LAN round over TCP on `--port`.
+    Serve,
+    /// Serve the JSON API over HTTP on `--port`.
+    Http,
+    /// Print the stored statistics, or wipe them with `stats reset`.
+    Stats { reset: bool },
+    /// Play a `--record`ed session again and check the output still matches.
+    Replay { path: PathBuf },
+    /// Print the effective settings and where each came from.
+    Config,
+    /// Print [`USAGE`].
+    Help,
+}
+
+impl Command {
+    /// The name it is given on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Play => "play",
+            Command::Reverse => "reverse",
+            Command::Mastermind => "mastermind",
+            Command::Speedrun => "speedrun",
+            Command::Adaptive => "adaptive",
+            Command::Daily => "daily",
+            Command::Tournament => "tournament",
+            Command::Bench => "bench",
+            Command::Serve => "serve",
+            Command::Http => "http",
+            Command::Stats { .. } => "stats",
+            Command::Replay { .. } => "replay",
+            Command::Config => "config",
+            Command::Help => "help",
+        }
+    }
+
+    /// Whether this command does anything with `flag`. `--lang` and
+    /// `--help` apply everywhere.
+    pub fn accepts(&self, flag: &str) -> bool {
+        let flags: &[&str] = match self {
+            Command::Play => &[
+                "--difficulty",
+                "--min",
+                "--max",
+                "--seed",
+                "--attempts",
+                "--color",
+                "--no-color",
+                "--stats-path",
+                "--hot-cold",
+                "--thresholds",
+                "--tui",
+                "--time-limit",
+                "--guess-time",
+                "--record",
+            ],
+            Command::Reverse => &["--difficulty", "--min", "--max"],
+            Command::Mastermind => &[
+                "--seed",
+                "--attempts",
+                "--time-limit",
+                "--guess-time",
+                "--length",
+                "--digits",
+                "--repeats",
+            ],
+            Command::Speedrun => &[
+                "--difficulty",
+                "--min",
+                "--max",
+                "--seed",
+                "--attempts",
+                "--time-limit",
+                "--guess-time",
+                "--rounds",
+            ],
+            Command::Adaptive => &["--seed", "--rounds", "--player", "--target", "--stats-path"],
+            Command::Daily => &["--ascii"],
+            Command::Tournament => &[
+                "--difficulty",
+                "--min",
+                "--max",
+                "--seed",
+                "--attempts",
+                "--rounds",
+                "--players",
+                "--shared",
+            ],
+            Command::Bench => &["--difficulty", "--min", "--max", "--seed", "--games"],
+            Command::Serve => &["--difficulty", "--min", "--max", "--seed", "--port"],
+            Command::Http => &["--port"],
+            Command::Stats { .. } => &["--stats-path"],
+            Command::Replay { .. } => &[],
+            Command::Config => &[
+                "--difficulty",
+                "--min",
+                "--max",
+                "--attempts",
+                "--color",
+                "--no-color",
+                "--stats-path",
+            ],
+            Command::Help => return true,
+        };
+        matches!(flag, "--lang" | "-h" | "--help") || flags.contains(&flag)
+    }
+}
+
+pub const USAGE: &str = "\
+Usage: guessing_game [COMMAND] [OPTIONS]
+
+Commands:
+  play            Guess the secret number (the default)
+  reverse         Think of a number and let the program guess it
+  mastermind      Break a secret code of digits
+  speedrun        Play several rounds against the clock
+  daily           Play today's puzzle, the same for everyone
+  tournament      Take turns with friends at one terminal
+  adaptive        Rounds that adjust to your skill rating
+  bench           Compare the solver strategies over many seeded games
+  serve           Host a multiplayer round over TCP
+  http            Serve the game as a JSON API over HTTP
+  stats [reset]   Show, or wipe, your statistics
+  replay FILE     Play a recorded session again and check it still matches
+  config show     Show the effective settings and where each came from
+  help            Show this message
+
+Options:
+  --difficulty D        easy, normal or hard
+  --min N --max N       Play on a custom range instead
+  --seed N              Seed the secret for a reproducible game
+  --attempts N          Lose after N wrong guesses
+  --lang L              Language: en, es or hi (default: from LANG)
+  --color, --no-color   Color the full-screen UI, or don't
+  --stats-path FILE     Keep statistics in FILE
+  --hot-cold            Answer with hot/cold instead of too small/too big
+  --thresholds A,B,C,D  Hot/cold band limits
+  --tui                 Play full-screen
+  --time-limit S        Seconds for the whole game
+  --guess-time S        Seconds for each guess
+  --record FILE         Save the session for `replay`
+  --length N            Mastermind code length
+  --digits N            Mastermind digits to choose from
+  --repeats             Allow repeated digits in Mastermind codes
+  --rounds N            Rounds for speedrun and tournament
+  --players A,B,...     2 to 8 player names for tournament
+  --shared              Tournament players all chase the same secret
+  --player NAME         Whose rating adaptive rounds use (default: $USER)
+  --target P            Adaptive win rate to aim for, 0 to 1 (default 0.7)
+  --ascii               Share the daily result without emoji
+  --games N             Games per strategy for bench
+  --port N              Port for serve and http
+  -h, --help            Show this message
+
+While playing, type a number to guess, or:
+  hint      Narrow the range, at a cost to your score
+  range     Show the numbers still possible
+  history   List your guesses so far
+  undo      Take back your last guess (it still costs the attempt)
+  giveup    End the game and reveal the secret
+  quit      Leave the game
+
+Settings not given as flags come from GUESSING_GAME_DIFFICULTY,
+GUESSING_GAME_ATTEMPTS, GUESSING_GAME_LANG, GUESSING_GAME_COLORS (or NO_COLOR)
+and GUESSING_GAME_STATS, then from ~/.config/guessing_game/config.toml
+(or $GUESSING_GAME_CONFIG), which may set difficulty, attempts, language,
+colors and stats-path.
+
+Exit status:
+  0  the game was won, or the command succeeded
+  1  the game was lost, or a replay did not match
+  2  the command line was invalid
+  3  the player quit before the game was decided
+  4  an I/O or other runtime error
+";
+
+/// How the process exits, so scripts can tell how a game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Success = 0,
+    Lost = 1,
+    Usage = 2,
+    Quit = 3,
+    Error = 4,
+}
+
+impl Exit {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+}
+
+impl From<Outcome> for Exit {
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::Won { .. } => Exit::Success,
+            Outcome::Lost { .. } => Exit::Lost,
+            Outcome::Quit { .. } => Exit::Quit,
+        }
+    }
+}
+
+/// Options accepted on the command line.
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub seed: Option<u64>,
+    /// `None` means the player should be asked which difficulty to play.
+    pub difficulty: Option<Difficulty>,
+    pub max_attempts: Option<u32>,
+    /// `--time-limit` and `--guess-time`, in seconds on the command line.
+    pub limits: TimeLimits,
+    /// Rounds for `speedrun` and `tournament`.
+    pub rounds: Option<u32>,
+    /// Names for `tournament`, in turn order.
+    pub players: Vec<String>,
+    /// `tournament` players share one secret per round.
+    pub shared_secret: bool,
+    /// Whose rating `adaptive` uses and updates.
+    pub player: Option<String>,
+    /// Win rate `adaptive` aims for.
+    pub target: Option<f64>,
+    /// Share the `daily` result as plain ASCII instead of emoji.
+    pub ascii: bool,
+    /// Rounds per strategy for `bench`.
+    pub games: Option<u32>,
+    /// Answer guesses with hot/cold bands instead of too small/too big.
+    pub hot_cold: bool,
+    /// Band limits for `--hot-cold`; defaults scale with the range.
+    pub thresholds: Option<Thresholds>,
+    /// Code length, digit count and repeats for `mastermind`.
+    pub code_length: Option<usize>,
+    pub digits: Option<u8>,
+    pub repeats: bool,
+    /// Play full-screen when the terminal allows it.
+    pub tui: bool,
+    /// TCP port for `serve` and `http`.
+    pub port: Option<u16>,
+    /// Save the session to this file so `replay` can check it later.
+    pub record: Option<PathBuf>,
+    /// `--lang`; `None` leaves it to the environment and config file.
+    pub lang: Option<Locale>,
+    /// `--color` or `--no-color`.
+    pub colors: Option<bool>,
+    pub stats_path: Option<PathBuf>,
+}
+
+impl Args {
+    pub fn parse<I: Iterator<Item = String>>(args: I) -> Result<Args, String> {
+        let mut parsed = Args::default();
+        let mut min = None;
+        let mut max = None;
+        let mut args = args.peekable();
+
+        match args.peek().map(String::as_str) {
+            Some("play") => {
+                args.next();
+            }
+            Some("reverse") => {
+                args.next();
+                parsed.command = Command::Reverse;
+            }
+            Some("mastermind") => {
+                args.next();
+                parsed.command = Command::Mastermind;
+            }
+            Some("speedrun") => {
+                args.next();
+                parsed.command = Command::Speedrun;
+            }
+            Some("tournament") => {
+                args.next();
+                parsed.command = Command::Tournament;
+            }
+            Some("daily") => {
+                args.next();
+                parsed.command = Command::Daily;
+            }
+            Some("adaptive") => {
+                args.next();
+                parsed.command = Command::Adaptive;
+            }
+            Some("bench") => {
+                args.next();
+                parsed.command = Command::Bench;
+            }
+            Some("serve") => {
+                args.next();
+                parsed.command = Command::Serve;
+            }
+            Some("http") => {
+                args.next();
+                parsed.command = Command::Http;
+            }
+            Some("stats") => {
+                args.next();
+                let reset = args.next_if(|arg| arg == "reset").is_some();
+                parsed.command = Command::Stats { reset };
+            }
+            Some("replay") => {
+                args.next();
+                let path = args.next().ok_or("replay needs a recording file")?;
+                parsed.command = Command::Replay { path: path.into() };
+            }
+            Some("config") => {
+                args.next();
+                args.next_if(|arg| arg == "show");
+                parsed.command = Command::Config;
+            }
+            Some("help") => {
+                parsed.command = Command::Help;
+                return Ok(parsed);
+            }
+            _ => {}
+        }
+
+        let mut given = Vec::new();
+        while let Some(arg) = args.next() {
+            given.push(arg.clone());
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    parsed.command = Command::Help;
+                    return Ok(parsed);
+                }
+                "--seed" => parsed.seed = Some(value(&mut args, "--seed")?),
+                "--difficulty" => parsed.difficulty = Some(value(&mut args, "--difficulty")?),
+                "--attempts" => {
+                    let max: u32 = value(&mut args, "--attempts")?;
+                    if max == 0 {
+                        return Err("--attempts must be at least 1".to_string());
+                    }
+                    parsed.max_attempts = Some(max);
+                }
+                "--time-limit" => {
+                    parsed.limits.per_game = Some(seconds(&mut args, "--time-limit")?)
+                }
+                "--guess-time" => {
+                    parsed.limits.per_guess = Some(seconds(&mut args, "--guess-time")?)
+                }
+                "--rounds" => {
+                    let rounds: u32 = value(&mut args, "--rounds")?;
+                    if rounds == 0 {
+                        return Err("--rounds must be at least 1".to_string());
+                    }
+                    parsed.rounds = Some(rounds);
+                }
+                "--games" => {
+                    let games: u32 = value(&mut args, "--games")?;
+                    if games == 0 {
+                        return Err("--games must be at least 1".to_string());
+                    }
+                    parsed.games = Some(games);
+                }
+                "--players" => {
+                    let names: String = value(&mut args, "--players")?;
+                    parsed.players = names
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .collect();
+                }
+                "--shared" => parsed.shared_secret = true,
+                "--ascii" => parsed.ascii = true,
+                "--player" => {
+                    let name: String = value(&mut args, "--player")?;
+                    if name.trim().is_empty() {
+                        return Err("--player cannot be blank".to_string());
+                    }
+                    parsed.player = Some(name.trim().to_string());
+                }
+                "--target" => {
+                    let target: f64 = value(&mut args, "--target")?;
+                    if !(target > 0.0 && target < 1.0) {
+                        return Err("--target must be between 0 and 1".to_string());
+                    }
+                    parsed.target = Some(target);
+                }
+                "--port" => parsed.port = Some(value(&mut args, "--port")?),
+                "--color" => parsed.colors = Some(true),
+                "--no-color" => parsed.colors = Some(false),
+                "--stats-path" => parsed.stats_path = Some(value(&mut args, "--stats-path")?),
+                "--lang" => parsed.lang = Some(value(&mut args, "--lang")?),
+                "--record" => parsed.record = Some(value(&mut args, "--record")?),
+                "--tui" => parsed.tui = true,
+                "--length" => parsed.code_length = Some(value(&mut args, "--length")?),
+                "--digits" => parsed.digits = Some(value(&mut args, "--digits")?),
+                "--repeats" => parsed.repeats = true,
+                "--hot-cold" => parsed.hot_cold = true,
+                "--thresholds" => {
+                    parsed.thresholds = Some(value(&mut args, "--thresholds")?);
+                    parsed.hot_cold = true;
+                }
+                "--min" => min = Some(value(&mut args, "--min")?),
+                "--max" => max = Some(value(&mut args, "--max")?),
+                other => return Err(format!("unknown argument '{other}'")),
+            }
+        }
+
+        if let Some(flag) = given.iter().find(|flag| !parsed.command.accepts(flag)) {
+            return Err(format!(
+                "{flag} does not apply to {}",
+                parsed.command.name()
+            ));
+        }
+
+        match (min, max) {
+            (None, None) => {}
+            (Some(min), Some(max)) => {
+                if parsed.difficulty.is_some() {
+                    return Err("--difficulty cannot be combined with --min/--max".to_string());
+                }
+                parsed.difficulty = Some(Difficulty::custom(min, max)?);
+            }
+            _ => return Err("--min and --max must be given together".to_string()),
+        }
+
+        if parsed.command == Command::Tournament {
+            tournament::check_players(&parsed.players)?;
+        }
+
+        // The full-screen UI has no clock and only the classic replies.
+        if parsed.tui && (parsed.hot_cold || parsed.limits.is_set()) {
+            return Err(
+                "--tui cannot be combined with --hot-cold, --time-limit or --guess-time"
+                    .to_string(),
+            );
+        }
+        if parsed.record.is_some() && (parsed.tui || parsed.limits.is_set()) {
+            return Err(
+                "--record cannot be combined with --tui, --time-limit or --guess-time".to_string(),
+            );
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Reads and parses the value following `flag`.
+fn value<T, I>(args: &mut I, flag: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+    I: Iterator<Item = String>,
+{
+    let raw = args.next().ok_or(format!("{flag} needs a value"))?;
+    raw.parse()
+        .map_err(|err| format!("invalid value '{raw}' for {flag}: {err}"))
+}
+
+/// Reads a positive number of seconds, fractions allowed.
+fn seconds<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<Duration, String> {
+    let seconds: f64 = value(args, flag)?;
+    let invalid = || format!("{flag} must be a positive number of seconds");
+    if seconds <= 0.0 {
+        return Err(invalid());
+    }
+    // Also rejects NaN, infinity and anything too big for a `Duration`.
+    Duration::try_from_secs_f64(seconds).map_err(|_| invalid())
+}
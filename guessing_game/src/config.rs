@@ -0,0 +1,298 @@
+use serde::Deserialize;
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::cli::Args;
+use crate::difficulty::Difficulty;
+use crate::i18n::{message, Locale};
+use crate::stats::StatsStore;
+
+/// The settings a config file may set. Every key is optional.
+///
+/// ```toml
+/// difficulty = "hard"        # or { custom = { min = 1, max = 500 } }
+/// attempts = 12
+/// language = "es"
+/// colors = false
+/// stats-path = "/home/me/games/stats.json"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ConfigFile {
+    pub difficulty: Option<Difficulty>,
+    pub attempts: Option<u32>,
+    pub language: Option<Locale>,
+    pub colors: Option<bool>,
+    pub stats_path: Option<PathBuf>,
+}
+
+impl ConfigFile {
+    pub fn parse(source: &str) -> Result<ConfigFile, String> {
+        let file: ConfigFile = toml::from_str(source).map_err(|err| err.message().to_string())?;
+        if let Some(Difficulty::Custom { min, max }) = file.difficulty {
+            Difficulty::custom(min, max)?;
+        }
+        if file.attempts == Some(0) {
+            return Err("attempts must be at least 1".to_string());
+        }
+        Ok(file)
+    }
+
+    /// Reads the file at `path`; a missing file is `None`, not an error.
+    pub fn load(path: &Path) -> Result<Option<ConfigFile>, String> {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(format!("could not read {}: {err}", path.display())),
+        };
+        ConfigFile::parse(&source)
+            .map(Some)
+            .map_err(|err| format!("bad config file {}: {err}", path.display()))
+    }
+}
+
+/// `$GUESSING_GAME_CONFIG`, else `$XDG_CONFIG_HOME/guessing_game/config.toml`,
+/// else `~/.config/guessing_game/config.toml`.
+pub fn config_path(env: &dyn Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    if let Some(path) = env("GUESSING_GAME_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let dir = match env("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env("HOME")?).join(".config"),
+    };
+    Some(dir.join("guessing_game").join("config.toml"))
+}
+
+/// Where a setting's value came from, strongest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    CommandLine,
+    /// Named environment variable.
+    Env(String),
+    ConfigFile,
+    Default,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Source::CommandLine => message("config.source.command-line", &[]),
+            Source::Env(name) => message("config.source.env", &[("name", name)]),
+            Source::ConfigFile => message("config.source.file", &[]),
+            Source::Default => message("config.source.default", &[]),
+        };
+        f.write_str(&text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+/// The effective settings after merging the command line, environment
+/// variables, the config file and the built-in defaults, in that order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Where the config file was looked for, and whether it was there.
+    pub path: Option<PathBuf>,
+    pub found: bool,
+    /// `None` means ask the player.
+    pub difficulty: Setting<Option<Difficulty>>,
+    /// `None` means unlimited.
+    pub attempts: Setting<Option<u32>>,
+    pub language: Setting<Locale>,
+    /// Color the full-screen UI.
+    pub colors: Setting<bool>,
+    /// `None` when there is no data directory to default to.
+    pub stats_path: Setting<Option<PathBuf>>,
+}
+
+impl Config {
+    /// Merges `args` with the real environment and config file.
+    pub fn load(args: &Args) -> Result<Config, String> {
+        let env = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        let path = config_path(&env);
+        let file = match &path {
+            Some(path) => ConfigFile::load(path)?,
+            None => None,
+        };
+        Config::resolve(args, &env, path, file)
+    }
+
+    /// Merges explicit sources; `env` looks up environment variables.
+    pub fn resolve(
+        args: &Args,
+        env: &dyn Fn(&str) -> Option<String>,
+        path: Option<PathBuf>,
+        file: Option<ConfigFile>,
+    ) -> Result<Config, String> {
+        let found = file.is_some();
+        let file = file.unwrap_or_default();
+
+        let difficulty = pick(
+            args.difficulty.map(Some),
+            from_env(env, "GUESSING_GAME_DIFFICULTY")?.map(|(name, d)| (name, Some(d))),
+            file.difficulty.map(Some),
+            default(None),
+        );
+
+        let attempts = match from_env::<u32>(env, "GUESSING_GAME_ATTEMPTS")? {
+            Some((name, 0)) => return Err(format!("{name} must be at least 1")),
+            found => pick(
+                args.max_attempts.map(Some),
+                found.map(|(name, n)| (name, Some(n))),
+                file.attempts.map(Some),
+                default(None),
+            ),
+        };
+
+        // The system locale applies only when nothing more specific does,
+        // since LANG is set almost everywhere.
+        let mut system = default(Locale::En);
+        for name in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+            if let Some(value) = env(name) {
+                if let Ok(locale) = value.parse() {
+                    system = Setting {
+                        value: locale,
+                        source: Source::Env(name.to_string()),
+                    };
+                }
+                break;
+            }
+        }
+        let language = pick(
+            args.lang,
+            from_env(env, "GUESSING_GAME_LANG")?,
+            file.language,
+            system,
+        );
+
+        let colors_env = match from_env(env, "GUESSING_GAME_COLORS")? {
+            Some(found) => Some(found),
+            // https://no-color.org: any non-empty value turns color off.
+            None => env("NO_COLOR").map(|_| ("NO_COLOR".to_string(), false)),
+        };
+        let colors = pick(args.colors, colors_env, file.colors, default(true));
+
+        let stats_path = pick(
+            args.stats_path.clone().map(Some),
+            env("GUESSING_GAME_STATS")
+                .map(|path| ("GUESSING_GAME_STATS".to_string(), Some(PathBuf::from(path)))),
+            file.stats_path.map(Some),
+            default(StatsStore::default_location().map(|store| store.path().to_path_buf())),
+        );
+
+        Ok(Config {
+            path,
+            found,
+            difficulty,
+            attempts,
+            language,
+            colors,
+            stats_path,
+        })
+    }
+
+    /// The stats file to use, if there is anywhere to keep one.
+    pub fn stats_store(&self) -> Option<StatsStore> {
+        self.stats_path.value.clone().map(StatsStore::new)
+    }
+
+    /// Prints every setting with its value and where it came from.
+    pub fn write_report<W: Write>(&self, mut output: W) -> io::Result<()> {
+        let file = match &self.path {
+            Some(path) if self.found => message("config.file", &[("path", &path.display())]),
+            Some(path) => message("config.file-missing", &[("path", &path.display())]),
+            None => message("config.no-file", &[]),
+        };
+        writeln!(output, "{file}")?;
+        writeln!(output)?;
+
+        let difficulty = match self.difficulty.value {
+            Some(difficulty) => difficulty.to_string(),
+            None => message("config.ask", &[]),
+        };
+        let attempts = match self.attempts.value {
+            Some(attempts) => attempts.to_string(),
+            None => message("config.unlimited", &[]),
+        };
+        let stats_path = match &self.stats_path.value {
+            Some(path) => path.display().to_string(),
+            None => message("config.none", &[]),
+        };
+        let rows = [
+            ("difficulty", difficulty, &self.difficulty.source),
+            ("attempts", attempts, &self.attempts.source),
+            (
+                "language",
+                self.language.value.to_string(),
+                &self.language.source,
+            ),
+            ("colors", self.colors.value.to_string(), &self.colors.source),
+            ("stats-path", stats_path, &self.stats_path.source),
+        ];
+        for (key, value, source) in rows {
+            writeln!(output, "{key:<11} = {value:<24} ({source})")?;
+        }
+        Ok(())
+    }
+}
+
+fn default<T>(value: T) -> Setting<T> {
+    Setting {
+        value,
+        source: Source::Default,
+    }
+}
+
+/// The first of command line, environment and file that has a value.
+fn pick<T>(
+    cli: Option<T>,
+    env: Option<(String, T)>,
+    file: Option<T>,
+    fallback: Setting<T>,
+) -> Setting<T> {
+    if let Some(value) = cli {
+        return Setting {
+            value,
+            source: Source::CommandLine,
+        };
+    }
+    if let Some((name, value)) = env {
+        return Setting {
+            value,
+            source: Source::Env(name),
+        };
+    }
+    if let Some(value) = file {
+        return Setting {
+            value,
+            source: Source::ConfigFile,
+        };
+    }
+    fallback
+}
+
+/// Reads and parses environment variable `name`, if set.
+fn from_env<T>(
+    env: &dyn Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<Option<(String, T)>, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let Some(raw) = env(name) else {
+        return Ok(None);
+    };
+    raw.parse()
+        .map(|value| Some((name.to_string(), value)))
+        .map_err(|err| format!("invalid value '{raw}' in {name}: {err}"))
+}
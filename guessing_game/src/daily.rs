@@ -0,0 +1,296 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome};
+use crate::i18n::message;
+use crate::input::LineSource;
+use crate::play::{direction, play_loop};
+use crate::timed::TimeLimits;
+
+/// A calendar day (proleptic Gregorian, UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date> {
+        let date = Date { year, month, day };
+        // Valid exactly when it survives a round trip through day numbers.
+        (Date::from_days(date.days()) == date).then_some(date)
+    }
+
+    /// Today in UTC, so everyone's puzzle changes at the same moment.
+    pub fn today() -> Date {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    /// Days since 1970-01-01.
+    pub fn days(&self) -> i64 {
+        // Howard Hinnant's days_from_civil.
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` after 1970-01-01.
+    pub fn from_days(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days.
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{s}' is not a date like 2024-03-01");
+        let mut parts = s.splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let (year, month, day) = (next()?, next()?, next()?);
+        Date::new(
+            year.parse().map_err(|_| invalid())?,
+            month.parse().map_err(|_| invalid())?,
+            day.parse().map_err(|_| invalid())?,
+        )
+        .ok_or_else(invalid)
+    }
+}
+
+/// Puzzle #1 was played on this day.
+const FIRST_DAY: Date = Date {
+    year: 2024,
+    month: 1,
+    day: 1,
+};
+
+/// Upper ends the daily range is picked from.
+const DAILY_MAXIMUMS: [i64; 5] = [50, 100, 200, 500, 1000];
+
+/// The day's shared puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub date: Date,
+    /// Counts from 1 on 2024-01-01.
+    pub number: i64,
+    pub difficulty: Difficulty,
+    pub secret: i64,
+    /// One more than bisection needs.
+    pub max_attempts: u32,
+}
+
+impl Puzzle {
+    /// The puzzle for `date`. It is derived from the date alone with a fixed
+    /// hash rather than a library generator, whose output may change between
+    /// versions, so every build agrees on it.
+    pub fn for_date(date: Date) -> Puzzle {
+        let mut state = date.days() as u64;
+        let max = DAILY_MAXIMUMS[(splitmix64(&mut state) % DAILY_MAXIMUMS.len() as u64) as usize];
+        let secret = 1 + (splitmix64(&mut state) % max as u64) as i64;
+        let difficulty = Difficulty::Custom { min: 1, max };
+        Puzzle {
+            date,
+            number: date.days() - FIRST_DAY.days() + 1,
+            difficulty,
+            secret,
+            max_attempts: difficulty.optimal_guesses() + 1,
+        }
+    }
+
+    pub fn game(&self) -> Game<StdRng> {
+        Game::with_difficulty(StdRng::seed_from_u64(self.secret as u64), self.difficulty)
+            .with_secret(self.secret)
+            .with_max_attempts(Some(self.max_attempts))
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// How a day's puzzle went, kept so the result can be shared again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub won: bool,
+    pub attempts: u32,
+    pub hints: u32,
+    /// One `^` (go higher), `v` (go lower) or `*` (found it) per attempt,
+    /// counting guesses that were taken back.
+    pub verdicts: String,
+}
+
+impl DailyResult {
+    fn new<R: rand::Rng>(game: &Game<R>, outcome: Outcome) -> DailyResult {
+        let verdicts = game
+            .guesses()
+            .iter()
+            .map(|&(_, ordering)| match ordering {
+                Ordering::Less => '^',
+                Ordering::Greater => 'v',
+                Ordering::Equal => '*',
+            })
+            .collect();
+        DailyResult {
+            won: matches!(outcome, Outcome::Won { .. }),
+            attempts: game.attempts(),
+            hints: game.hints(),
+            verdicts,
+        }
+    }
+
+    /// The text to paste into chat: a header and the verdicts in rows of
+    /// five, as emoji or, with `ascii`, as the plain symbols.
+    pub fn share(&self, puzzle: &Puzzle, ascii: bool) -> String {
+        let score = if self.won {
+            self.attempts.to_string()
+        } else {
+            "X".to_string()
+        };
+        let mut text = format!(
+            "Guessing Game #{} {} {score}/{}",
+            puzzle.number, puzzle.date, puzzle.max_attempts
+        );
+        if self.hints > 0 {
+            text.push_str(&format!(" (hints: {})", self.hints));
+        }
+
+        let symbols: Vec<&str> = self
+            .verdicts
+            .chars()
+            .map(|verdict| match (verdict, ascii) {
+                ('^', false) => "⬆️",
+                ('v', false) => "⬇️",
+                ('*', false) => "✅",
+                ('^', true) => "^",
+                ('v', true) => "v",
+                _ => "*",
+            })
+            .collect();
+        for row in symbols.chunks(5) {
+            text.push('\n');
+            text.push_str(&row.concat());
+        }
+        text
+    }
+}
+
+/// Every day a puzzle was started, with its result once finished.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyLog {
+    /// Keyed by date. `None` means started but never finished, which still
+    /// uses up the day.
+    pub played: BTreeMap<String, Option<DailyResult>>,
+}
+
+impl DailyLog {
+    /// Reads the log; a missing file is an empty log. A damaged file is an
+    /// error rather than a fresh start, which would hand out replays.
+    pub fn load(path: &Path) -> io::Result<DailyLog> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(DailyLog::default()),
+            Err(err) => return Err(err),
+        };
+        serde_json::from_str(&contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Writes through a temporary file, like the stats.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::from)?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)
+    }
+
+    /// `Some` if `date` was already played, with the result if it finished.
+    pub fn get(&self, date: Date) -> Option<&Option<DailyResult>> {
+        self.played.get(&date.to_string())
+    }
+
+    pub fn start(&mut self, date: Date) {
+        self.played.insert(date.to_string(), None);
+    }
+
+    pub fn finish(&mut self, date: Date, result: DailyResult) {
+        self.played.insert(date.to_string(), Some(result));
+    }
+}
+
+/// Plays the day's puzzle once. Quitting counts as a loss: the day is
+/// used up either way.
+pub fn play_daily<I: LineSource, O: Write>(
+    puzzle: &Puzzle,
+    mut input: I,
+    mut output: O,
+) -> io::Result<DailyResult> {
+    let mut game = puzzle.game();
+    writeln!(
+        output,
+        "{}",
+        message(
+            "daily.start",
+            &[("number", &puzzle.number), ("date", &puzzle.date)]
+        )
+    )?;
+    let outcome = play_loop(
+        &mut game,
+        &mut input,
+        &mut output,
+        direction,
+        &TimeLimits::default(),
+        true,
+    )?;
+    Ok(DailyResult::new(&game, outcome))
+}
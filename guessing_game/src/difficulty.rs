@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// How wide the range of possible secret numbers is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Custom {
+        min: i64,
+        max: i64,
+    },
+}
+
+impl Difficulty {
+    /// Builds a custom difficulty, rejecting ranges that hold fewer than two
+    /// numbers.
+    pub fn custom(min: i64, max: i64) -> Result<Difficulty, String> {
+        if min >= max {
+            return Err(format!("minimum {min} must be smaller than maximum {max}"));
+        }
+        Ok(Difficulty::Custom { min, max })
+    }
+
+    pub fn range(&self) -> RangeInclusive<i64> {
+        match *self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=10_000,
+            Difficulty::Custom { min, max } => min..=max,
+        }
+    }
+
+    /// How many numbers the secret can be.
+    pub fn size(&self) -> u128 {
+        let range = self.range();
+        (*range.end() as i128 - *range.start() as i128 + 1) as u128
+    }
+
+    /// `ceil(log2(size))`, the number of guesses a perfect bisection needs.
+    pub fn optimal_guesses(&self) -> u32 {
+        let size = self.size();
+        if size <= 1 {
+            0
+        } else {
+            u128::BITS - (size - 1).leading_zeros()
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "easy"),
+            Difficulty::Normal => write!(f, "normal"),
+            Difficulty::Hard => write!(f, "hard"),
+            Difficulty::Custom { min, max } => write!(f, "custom {min}..={max}"),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    /// Parses one of the named presets; custom ranges come from `--min`/`--max`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            other => Err(format!(
+                "unknown difficulty '{other}' (expected easy, normal or hard)"
+            )),
+        }
+    }
+}
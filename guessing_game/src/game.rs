@@ -0,0 +1,235 @@
+use rand::Rng;
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use crate::difficulty::Difficulty;
+use crate::reverse::Guesser;
+
+/// Points a winning score loses for every hint taken.
+pub const HINT_PENALTY: u32 = 10;
+
+/// How a finished game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won {
+        attempts: u32,
+        score: u32,
+    },
+    Lost {
+        attempts: u32,
+        secret_number: i64,
+    },
+    /// The player left before the game was decided.
+    Quit {
+        attempts: u32,
+    },
+}
+
+/// What one guess did: how it compared to the secret, and how the game
+/// ended if that guess ended it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn {
+    pub ordering: Ordering,
+    pub outcome: Option<Outcome>,
+}
+
+/// A single guessing game driven by an injectable random number generator.
+///
+/// Passing a seeded generator (e.g. `StdRng::seed_from_u64`) makes the secret
+/// number, and therefore the whole session, reproducible.
+pub struct Game<R: Rng> {
+    rng: R,
+    difficulty: Difficulty,
+    secret_number: i64,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    history: Vec<(i64, Ordering)>,
+    /// Every guess made this round, including any taken back.
+    guesses: Vec<(i64, Ordering)>,
+    /// Where hints have said the secret is; the whole range before any.
+    hinted: RangeInclusive<i64>,
+    hints: u32,
+}
+
+impl<R: Rng> Game<R> {
+    /// Starts a game on the normal 1..=100 range.
+    pub fn new(rng: R) -> Self {
+        Self::with_difficulty(rng, Difficulty::Normal)
+    }
+
+    pub fn with_difficulty(mut rng: R, difficulty: Difficulty) -> Self {
+        let secret_number = rng.gen_range(difficulty.range());
+        Self {
+            rng,
+            difficulty,
+            secret_number,
+            attempts: 0,
+            max_attempts: None,
+            history: Vec::new(),
+            guesses: Vec::new(),
+            hinted: difficulty.range(),
+            hints: 0,
+        }
+    }
+
+    /// Uses `secret` instead of the number drawn from the generator, for
+    /// puzzles everyone must share. `secret` must be inside the range.
+    pub fn with_secret(mut self, secret: i64) -> Self {
+        assert!(
+            self.difficulty.range().contains(&secret),
+            "secret {secret} is outside {}",
+            self.difficulty
+        );
+        self.secret_number = secret;
+        self
+    }
+
+    /// Limits how many guesses the player gets before losing.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn range(&self) -> RangeInclusive<i64> {
+        self.difficulty.range()
+    }
+
+    pub fn secret_number(&self) -> i64 {
+        self.secret_number
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// Guesses left before the game is lost, or `None` without a limit.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    /// Compares a guess against the secret number.
+    pub fn check(&self, guess: i64) -> Ordering {
+        guess.cmp(&self.secret_number)
+    }
+
+    /// Every guess this round with how it compared, oldest first.
+    pub fn history(&self) -> &[(i64, Ordering)] {
+        &self.history
+    }
+
+    /// Every guess this round, oldest first, including any taken back with
+    /// [`undo`](Self::undo): one per attempt.
+    pub fn guesses(&self) -> &[(i64, Ordering)] {
+        &self.guesses
+    }
+
+    /// How many hints have been taken this round.
+    pub fn hints(&self) -> u32 {
+        self.hints
+    }
+
+    /// The numbers hints have not ruled out, ignoring the guesses.
+    pub fn hinted_range(&self) -> RangeInclusive<i64> {
+        self.hinted.clone()
+    }
+
+    /// The numbers neither hints nor the guesses so far have ruled out.
+    pub fn possible_range(&self) -> RangeInclusive<i64> {
+        let mut guesser = Guesser::new(self.hinted_range());
+        for &(guess, ordering) in &self.history {
+            if ordering == Ordering::Equal {
+                return guess..=guess;
+            }
+            let _ = guesser.narrow(guess, ordering);
+        }
+        guesser.remaining()
+    }
+
+    /// Halves `around`, keeping the half that holds the secret, and costs
+    /// [`HINT_PENALTY`] points off a win. `around` is what the player already
+    /// knows: [`possible_range`](Self::possible_range) in the classic game,
+    /// [`hinted_range`](Self::hinted_range) when guesses don't say which
+    /// way to go. `None` when there is nothing left to narrow.
+    pub fn hint(&mut self, around: RangeInclusive<i64>) -> Option<RangeInclusive<i64>> {
+        let (low, high) = (*around.start(), *around.end());
+        if low >= high || !around.contains(&self.secret_number) {
+            return None;
+        }
+        let middle = ((low as i128 + high as i128).div_euclid(2)) as i64;
+        self.hinted = if self.secret_number <= middle {
+            low..=middle
+        } else {
+            middle + 1..=high
+        };
+        self.hints += 1;
+        Some(self.hinted_range())
+    }
+
+    /// Compares a guess and counts it as an attempt.
+    pub fn guess(&mut self, guess: i64) -> Ordering {
+        let ordering = self.check(guess);
+        self.attempts += 1;
+        self.history.push((guess, ordering));
+        self.guesses.push((guess, ordering));
+        ordering
+    }
+
+    /// Takes back the last guess, which is forgotten by
+    /// [`history`](Self::history) and [`possible_range`](Self::possible_range)
+    /// but still counts as an attempt, so undoing never buys a free guess.
+    /// `None` when there is no guess to take back.
+    pub fn undo(&mut self) -> Option<i64> {
+        self.history.pop().map(|(guess, _)| guess)
+    }
+
+    /// Makes a guess and works out whether it won or used the last attempt.
+    /// Every front end plays through this so the rules live in one place.
+    pub fn take_turn(&mut self, guess: i64) -> Turn {
+        let ordering = self.guess(guess);
+        let outcome = if ordering == Ordering::Equal {
+            Some(Outcome::Won {
+                attempts: self.attempts,
+                score: self.score(),
+            })
+        } else if self.attempts_left() == Some(0) {
+            Some(Outcome::Lost {
+                attempts: self.attempts,
+                secret_number: self.secret_number,
+            })
+        } else {
+            None
+        };
+        Turn { ordering, outcome }
+    }
+
+    /// Scores a win out of 100: full marks for matching the optimal number of
+    /// bisection guesses, proportionally less for every extra guess, and
+    /// [`HINT_PENALTY`] less for every hint.
+    pub fn score(&self) -> u32 {
+        let optimal = self.difficulty.optimal_guesses().max(1);
+        let attempts = self.attempts.max(1);
+        (100 * optimal / attempts)
+            .min(100)
+            .saturating_sub(HINT_PENALTY * self.hints)
+    }
+
+    /// Draws the next secret number from the same generator, so a seeded
+    /// game yields the same sequence of secrets every time.
+    pub fn next_round(&mut self) {
+        self.secret_number = self.rng.gen_range(self.difficulty.range());
+        self.attempts = 0;
+        self.history.clear();
+        self.guesses.clear();
+        self.hinted = self.difficulty.range();
+        self.hints = 0;
+    }
+}
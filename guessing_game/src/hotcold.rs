@@ -0,0 +1,229 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::game::{Game, Outcome};
+use crate::i18n::message;
+use crate::input::LineSource;
+use crate::play::{play_loop, Replies};
+use crate::timed::TimeLimits;
+
+/// How close a guess is to the secret, coldest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Band {
+    Freezing,
+    Cold,
+    Warm,
+    Hot,
+    Burning,
+}
+
+impl fmt::Display for Band {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let key = match self {
+            Band::Freezing => "hotcold.freezing",
+            Band::Cold => "hotcold.cold",
+            Band::Warm => "hotcold.warm",
+            Band::Hot => "hotcold.hot",
+            Band::Burning => "hotcold.burning",
+        };
+        write!(f, "{}", message(key, &[]))
+    }
+}
+
+/// Whether a guess got closer to the secret than the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Warmer,
+    Colder,
+    Same,
+}
+
+/// The largest distance from the secret that still counts as each band.
+/// Anything further than `cold` is freezing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Thresholds {
+    pub burning: u64,
+    pub hot: u64,
+    pub warm: u64,
+    pub cold: u64,
+}
+
+impl Thresholds {
+    pub fn new(burning: u64, hot: u64, warm: u64, cold: u64) -> Result<Thresholds, String> {
+        if burning < hot && hot < warm && warm < cold {
+            Ok(Thresholds {
+                burning,
+                hot,
+                warm,
+                cold,
+            })
+        } else {
+            Err(format!(
+                "thresholds must increase: burning {burning} < hot {hot} < warm {warm} < cold {cold}"
+            ))
+        }
+    }
+
+    /// Bands at 1%, 5%, 10% and 25% of the range size, so 1, 5, 10 and 25
+    /// on the normal 1..=100 range. Tiny ranges get at least 1, 2, 3 and 4.
+    pub fn for_range_size(size: u128) -> Thresholds {
+        let percent = |p: u128, floor: u64| ((size * p / 100) as u64).max(floor);
+        let burning = percent(1, 1);
+        let hot = percent(5, burning + 1);
+        let warm = percent(10, hot + 1);
+        let cold = percent(25, warm + 1);
+        Thresholds {
+            burning,
+            hot,
+            warm,
+            cold,
+        }
+    }
+
+    pub fn band(&self, distance: u64) -> Band {
+        if distance <= self.burning {
+            Band::Burning
+        } else if distance <= self.hot {
+            Band::Hot
+        } else if distance <= self.warm {
+            Band::Warm
+        } else if distance <= self.cold {
+            Band::Cold
+        } else {
+            Band::Freezing
+        }
+    }
+}
+
+impl FromStr for Thresholds {
+    type Err = String;
+
+    /// Parses `burning,hot,warm,cold`, e.g. `1,5,10,25`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Vec<u64> = s
+            .split(',')
+            .map(|part| part.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("'{s}' is not four comma-separated numbers"))?;
+        match values[..] {
+            [burning, hot, warm, cold] => Thresholds::new(burning, hot, warm, cold),
+            _ => Err(format!("'{s}' is not four comma-separated numbers")),
+        }
+    }
+}
+
+/// One hot/cold reply: the band, and the trend from the previous guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Feedback {
+    pub band: Band,
+    pub trend: Option<Trend>,
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            message("hotcold.feedback", &[("band", &self.band)])
+        )?;
+        let trend = match self.trend {
+            Some(Trend::Warmer) => "hotcold.warmer",
+            Some(Trend::Colder) => "hotcold.colder",
+            Some(Trend::Same) => "hotcold.same",
+            None => return Ok(()),
+        };
+        write!(f, " {}", message(trend, &[]))
+    }
+}
+
+/// Remembers the previous guesses so it can say warmer or colder.
+#[derive(Debug, Clone)]
+pub struct HotCold {
+    thresholds: Thresholds,
+    /// How far off each guess still standing was, oldest first.
+    distances: Vec<u64>,
+}
+
+impl HotCold {
+    pub fn new(thresholds: Thresholds) -> Self {
+        Self {
+            thresholds,
+            distances: Vec::new(),
+        }
+    }
+
+    pub fn feedback(&mut self, guess: i64, secret: i64) -> Feedback {
+        let distance = guess.abs_diff(secret);
+        let trend = self
+            .distances
+            .last()
+            .map(|previous| match distance.cmp(previous) {
+                Ordering::Less => Trend::Warmer,
+                Ordering::Greater => Trend::Colder,
+                Ordering::Equal => Trend::Same,
+            });
+        self.distances.push(distance);
+
+        Feedback {
+            band: self.thresholds.band(distance),
+            trend,
+        }
+    }
+
+    /// Forgets the last guess, so the next trend compares against the one
+    /// before it.
+    pub fn undo(&mut self) {
+        self.distances.pop();
+    }
+}
+
+/// Plays `game` with hot/cold replies instead of too small/too big.
+pub fn play_hot_cold<R: Rng, I: LineSource, O: Write>(
+    game: &mut Game<R>,
+    thresholds: Thresholds,
+    input: I,
+    output: O,
+) -> io::Result<Outcome> {
+    play_hot_cold_timed(game, thresholds, TimeLimits::default(), input, output)
+}
+
+/// [`play_hot_cold`] against the clock, with the same limits as
+/// [`crate::timed::play_timed`].
+pub fn play_hot_cold_timed<R: Rng, I: LineSource, O: Write>(
+    game: &mut Game<R>,
+    thresholds: Thresholds,
+    limits: TimeLimits,
+    mut input: I,
+    output: O,
+) -> io::Result<Outcome> {
+    let replies = HotColdReplies {
+        hot_cold: HotCold::new(thresholds),
+        secret: game.secret_number(),
+    };
+
+    // Hot/cold doesn't say which way to go, so hints mustn't either.
+    play_loop(game, &mut input, output, replies, &limits, false)
+}
+
+struct HotColdReplies {
+    hot_cold: HotCold,
+    secret: i64,
+}
+
+impl Replies for HotColdReplies {
+    fn reply(&mut self, guess: i64, ordering: Ordering) -> String {
+        if ordering == Ordering::Equal {
+            message("game.you-win", &[])
+        } else {
+            self.hot_cold.feedback(guess, self.secret).to_string()
+        }
+    }
+
+    fn undo(&mut self) {
+        self.hot_cold.undo();
+    }
+}
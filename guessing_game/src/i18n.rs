@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// A language the game can talk in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    Es,
+    Hi,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::En, Locale::Es, Locale::Hi];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Es => "es",
+            Locale::Hi => "hi",
+        }
+    }
+
+    /// Whether `count` takes the singular form in this language.
+    fn is_one(self, count: u32) -> bool {
+        match self {
+            Locale::En | Locale::Es => count == 1,
+            // Hindi uses the singular for zero as well.
+            Locale::Hi => count <= 1,
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::En => include_str!("../locales/en.txt"),
+            Locale::Es => include_str!("../locales/es.txt"),
+            Locale::Hi => include_str!("../locales/hi.txt"),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Accepts a bare language (`es`) or a POSIX locale (`es_MX.UTF-8`).
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Ok(Locale::En),
+            "es" => Ok(Locale::Es),
+            "hi" => Ok(Locale::Hi),
+            _ => Err(format!(
+                "unsupported language '{s}' (expected en, es or hi)"
+            )),
+        }
+    }
+}
+
+/// One language's messages, parsed from its bundle in `locales/`.
+///
+/// A bundle has one `key = text` per line; blank lines and lines starting
+/// with `#` are ignored. `{name}` in the text is filled in from the
+/// arguments. Counted messages have a `.one` and a `.other` key.
+#[derive(Debug)]
+pub struct Catalog {
+    messages: HashMap<&'static str, &'static str>,
+}
+
+impl Catalog {
+    pub fn parse(source: &'static str) -> Result<Catalog, String> {
+        let mut messages = HashMap::new();
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, text) = line
+                .split_once('=')
+                .ok_or(format!("line {}: expected 'key = text'", number + 1))?;
+            if messages.insert(key.trim(), text.trim()).is_some() {
+                return Err(format!(
+                    "line {}: duplicate key '{}'",
+                    number + 1,
+                    key.trim()
+                ));
+            }
+        }
+        Ok(Catalog { messages })
+    }
+
+    /// The catalog built into the binary for `locale`.
+    pub fn for_locale(locale: Locale) -> &'static Catalog {
+        static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+        let catalogs = CATALOGS.get_or_init(|| {
+            Locale::ALL
+                .iter()
+                .map(|&locale| {
+                    let catalog = Catalog::parse(locale.source())
+                        .unwrap_or_else(|err| panic!("bad {locale} bundle: {err}"));
+                    (locale, catalog)
+                })
+                .collect()
+        });
+        &catalogs[&locale]
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'static str> {
+        self.messages.get(key).copied()
+    }
+
+    /// Every key, sorted.
+    pub fn keys(&self) -> Vec<&'static str> {
+        let mut keys: Vec<_> = self.messages.keys().copied().collect();
+        keys.sort_unstable();
+        keys
+    }
+}
+
+thread_local! {
+    static CURRENT: Cell<Locale> = const { Cell::new(Locale::En) };
+}
+
+/// Switches the language messages are looked up in. The setting is per
+/// thread, so each test (and each server connection) starts in English.
+pub fn set_locale(locale: Locale) {
+    CURRENT.with(|current| current.set(locale));
+}
+
+pub fn locale() -> Locale {
+    CURRENT.with(Cell::get)
+}
+
+/// Looks up `key` in the current language, falling back to English, and
+/// fills in its `{placeholders}` from `args`.
+pub fn message(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    let text = Catalog::for_locale(locale())
+        .get(key)
+        .or_else(|| Catalog::for_locale(Locale::En).get(key))
+        .unwrap_or(key);
+    fill(text, args)
+}
+
+/// Like [`message`], but picks the `.one` or `.other` form of `key` for
+/// `count`, which is also available to the text as `{count}`.
+pub fn plural(key: &str, count: u32, args: &[(&str, &dyn Display)]) -> String {
+    let form = if locale().is_one(count) {
+        "one"
+    } else {
+        "other"
+    };
+    let mut all: Vec<(&str, &dyn Display)> = vec![("count", &count)];
+    all.extend_from_slice(args);
+    message(&format!("{key}.{form}"), &all)
+}
+
+/// The `{name}` placeholders used in `text`, in order.
+pub fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            break;
+        };
+        names.push(&rest[open + 1..open + close]);
+        rest = &rest[open + close + 1..];
+    }
+    names
+}
+
+fn fill(text: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}') else {
+            break;
+        };
+        let name = &rest[open + 1..open + close];
+        filled.push_str(&rest[..open]);
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => filled.push_str(&value.to_string()),
+            // Leave unknown placeholders visible rather than dropping text.
+            None => filled.push_str(&rest[open..=open + close]),
+        }
+        rest = &rest[open + close + 1..];
+    }
+    filled.push_str(rest);
+    filled
+}
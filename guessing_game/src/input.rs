@@ -0,0 +1,198 @@
+use std::fmt;
+use std::io::{self, BufRead};
+use std::num::IntErrorKind;
+use std::ops::RangeInclusive;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::i18n::message;
+
+/// Why a line typed at the guess prompt could not be used as a guess.
+#[derive(Debug)]
+pub enum InputError {
+    /// The line was blank.
+    Empty,
+    /// The line was not a whole number.
+    NotANumber(String),
+    /// A number, but outside the range the secret is drawn from.
+    OutOfRange {
+        guess: String,
+        min: i64,
+        max: i64,
+    },
+    /// Input was closed (end of file or Ctrl-D).
+    Eof,
+    Io(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            InputError::Empty => message("input.empty", &[]),
+            InputError::NotANumber(text) => message("input.not-a-number", &[("text", text)]),
+            InputError::OutOfRange { guess, min, max } => message(
+                "input.out-of-range",
+                &[("guess", guess), ("min", min), ("max", max)],
+            ),
+            InputError::Eof => message("input.eof", &[]),
+            InputError::Io(err) => message("input.io", &[("error", err)]),
+        };
+        write!(f, "{text}")
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<io::Error> for InputError {
+    fn from(err: io::Error) -> Self {
+        InputError::Io(err)
+    }
+}
+
+/// Parses one line typed at the prompt into a guess inside `range`.
+pub fn parse_guess(line: &str, range: &RangeInclusive<i64>) -> Result<i64, InputError> {
+    let text = line.trim();
+    if text.is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    let out_of_range = || InputError::OutOfRange {
+        guess: text.to_string(),
+        min: *range.start(),
+        max: *range.end(),
+    };
+
+    match text.parse::<i64>() {
+        Ok(guess) if range.contains(&guess) => Ok(guess),
+        Ok(_) => Err(out_of_range()),
+        Err(err) => match err.kind() {
+            // Too big even for an i64, but still clearly a number.
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => Err(out_of_range()),
+            _ => Err(InputError::NotANumber(text.to_string())),
+        },
+    }
+}
+
+/// Something typed at the guess prompt: a guess, or one of the reserved
+/// words that ask the game for something instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Guess(i64),
+    /// Narrow the range at the cost of some score.
+    Hint,
+    /// Leave without finishing.
+    Quit,
+    /// End the game as a loss and reveal the secret.
+    GiveUp,
+    /// List the guesses so far.
+    History,
+    /// Show the numbers still possible.
+    Range,
+    /// Take back the last guess; it still counts as an attempt.
+    Undo,
+}
+
+/// Parses one line typed at the prompt into a [`Command`]. Reserved words
+/// are matched case-insensitively; anything else must be a guess.
+pub fn parse_command(line: &str, range: &RangeInclusive<i64>) -> Result<Command, InputError> {
+    match reserved_word(line) {
+        Some(command) => Ok(command),
+        None => parse_guess(line, range).map(Command::Guess),
+    }
+}
+
+/// The [`Command`] `line` names if it is one of the reserved words, for
+/// prompts that read something other than a number in between.
+pub fn reserved_word(line: &str) -> Option<Command> {
+    let command = match line.trim().to_ascii_lowercase().as_str() {
+        "hint" => Command::Hint,
+        "quit" => Command::Quit,
+        "giveup" | "give up" => Command::GiveUp,
+        "history" => Command::History,
+        "range" => Command::Range,
+        "undo" => Command::Undo,
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Reads the next line and parses it with [`parse_guess`].
+pub fn read_guess<I: BufRead>(
+    input: &mut I,
+    range: &RangeInclusive<i64>,
+) -> Result<i64, InputError> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(InputError::Eof);
+    }
+    parse_guess(&line, range)
+}
+
+/// One read from a [`LineSource`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line {
+    Text(String),
+    Eof,
+    /// Nothing arrived before the timeout.
+    TimedOut,
+}
+
+/// Somewhere the game loop gets lines from, optionally giving up after a
+/// timeout. Any `BufRead` is a source that never times out.
+pub trait LineSource {
+    fn next_line(&mut self, timeout: Option<Duration>) -> io::Result<Line>;
+}
+
+impl<B: BufRead> LineSource for B {
+    fn next_line(&mut self, _timeout: Option<Duration>) -> io::Result<Line> {
+        let mut line = String::new();
+        if self.read_line(&mut line)? == 0 {
+            return Ok(Line::Eof);
+        }
+        Ok(Line::Text(line))
+    }
+}
+
+/// Lines read on a background thread, so waiting for the next one can time
+/// out even though reading stdin blocks.
+pub struct ChannelLines {
+    receiver: Receiver<io::Result<String>>,
+}
+
+impl ChannelLines {
+    /// Reads `reader` line by line on a new thread until it ends.
+    pub fn spawn<R: BufRead + Send + 'static>(reader: R) -> ChannelLines {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in reader.lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        ChannelLines { receiver }
+    }
+
+    /// Lines sent on a channel; dropping the sender is end of input.
+    pub fn from_receiver(receiver: Receiver<io::Result<String>>) -> ChannelLines {
+        ChannelLines { receiver }
+    }
+}
+
+impl LineSource for ChannelLines {
+    fn next_line(&mut self, timeout: Option<Duration>) -> io::Result<Line> {
+        let received = match timeout {
+            Some(timeout) => self.receiver.recv_timeout(timeout),
+            None => self
+                .receiver
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match received {
+            Ok(line) => line.map(Line::Text),
+            Err(RecvTimeoutError::Timeout) => Ok(Line::TimedOut),
+            Err(RecvTimeoutError::Disconnected) => Ok(Line::Eof),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+pub mod adaptive;
+pub mod api;
+pub mod cli;
+pub mod config;
+pub mod daily;
+pub mod difficulty;
+pub mod game;
+pub mod hotcold;
+pub mod i18n;
+pub mod input;
+pub mod mastermind;
+pub mod play;
+pub mod replay;
+pub mod reverse;
+pub mod server;
+pub mod stats;
+pub mod strategy;
+pub mod timed;
+pub mod tournament;
+pub mod tui;
@@ -1,35 +1,451 @@
-use std::io;
-use rand::Rng;
-use std::cmp::Ordering;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::error::Error;
+use std::io::{self, BufReader, IsTerminal};
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use guessing_game::adaptive::{self, play_adaptive_round};
+use guessing_game::api::{Api, HttpServer};
+use guessing_game::cli::{Args, Command, Exit, USAGE};
+use guessing_game::config::Config;
+use guessing_game::daily::{play_daily, DailyLog, Date, Puzzle};
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{Game, Outcome};
+use guessing_game::hotcold::{play_hot_cold, play_hot_cold_timed, Thresholds};
+use guessing_game::i18n::{self, message, plural};
+use guessing_game::input::ChannelLines;
+use guessing_game::mastermind::{play_mastermind, play_mastermind_timed, CodeOutcome, Rules};
+use guessing_game::play::{choose_difficulty, play};
+use guessing_game::replay::{self, Recording, Settings};
+use guessing_game::reverse::{play_reverse, ReverseOutcome};
+use guessing_game::server::Server;
+use guessing_game::stats::{data_dir, Stats, StatsError, StatsStore};
+use guessing_game::strategy;
+use guessing_game::timed::{self, play_timed};
+use guessing_game::tournament::{play_tournament, Tournament};
+use guessing_game::tui::play_tui;
 
 fn main() {
-    println!("Guess the number!");
+    let mut args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {message}");
+            eprintln!("Run with --help for usage.");
+            process::exit(Exit::Usage.code());
+        }
+    };
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
+    if args.command == Command::Help {
+        print!("{USAGE}");
+        process::exit(Exit::Success.code());
+    }
 
-    loop {
-        println!("Please input your guess.");
+    let config = match Config::load(&args) {
+        Ok(config) => config,
+        Err(message) => {
+            eprintln!("error: {message}");
+            process::exit(Exit::Usage.code());
+        }
+    };
+    // From here on the flags hold the merged settings.
+    args.difficulty = config.difficulty.value;
+    args.max_attempts = config.attempts.value;
+    i18n::set_locale(config.language.value);
 
-        let mut guess = String::new();
+    let result = match args.command {
+        Command::Play => run(&args, &config),
+        Command::Reverse => reverse(&args),
+        Command::Mastermind => mastermind(&args),
+        Command::Speedrun => speedrun(&args),
+        Command::Daily => daily(&args),
+        Command::Tournament => tournament(&args),
+        Command::Adaptive => adaptive(&args, &config),
+        Command::Bench => bench(&args),
+        Command::Serve => serve(&args),
+        Command::Http => http(&args),
+        Command::Stats { reset } => stats(reset, &config),
+        Command::Replay { ref path } => replay(path),
+        Command::Config => config
+            .write_report(io::stdout().lock())
+            .map(|()| Exit::Success)
+            .map_err(Into::into),
+        Command::Help => unreachable!("handled before loading the config"),
+    };
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line!");
+    let exit = result.unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+        Exit::Error
+    });
+    process::exit(exit.code());
+}
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+fn rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// The difficulty from the flags, or asked for interactively. `None` means
+/// the player closed input instead of choosing.
+fn difficulty(args: &Args) -> io::Result<Option<Difficulty>> {
+    match args.difficulty {
+        Some(difficulty) => Ok(Some(difficulty)),
+        // Only ask when someone is there to answer; piped input gets the default.
+        None if io::stdin().is_terminal() => {
+            choose_difficulty(io::stdin().lock(), io::stdout().lock())
+        }
+        None => Ok(Some(Difficulty::Normal)),
+    }
+}
+
+fn run(args: &Args, config: &Config) -> Result<Exit, Box<dyn Error>> {
+    let Some(difficulty) = difficulty(args)? else {
+        return Ok(Exit::Quit);
+    };
+
+    let thresholds = args
+        .thresholds
+        .unwrap_or_else(|| Thresholds::for_range_size(difficulty.size()));
+    let mut game =
+        Game::with_difficulty(rng(args.seed), difficulty).with_max_attempts(args.max_attempts);
+
+    let outcome = if let Some(path) = &args.record {
+        // A recording needs a known seed to be replayable.
+        let seed = args.seed.unwrap_or_else(rand::random);
+        let settings = Settings {
+            difficulty,
+            max_attempts: args.max_attempts,
+            hot_cold: args.hot_cold.then_some(thresholds),
+            locale: i18n::locale(),
         };
+        let (outcome, recording) =
+            replay::record(seed, settings, io::stdin().lock(), io::stdout().lock())?;
+        recording.save(path)?;
+        outcome
+    } else if args.hot_cold && args.limits.is_set() {
+        let lines = ChannelLines::spawn(BufReader::new(io::stdin()));
+        play_hot_cold_timed(
+            &mut game,
+            thresholds,
+            args.limits,
+            lines,
+            io::stdout().lock(),
+        )?
+    } else if args.hot_cold {
+        play_hot_cold(
+            &mut game,
+            thresholds,
+            io::stdin().lock(),
+            io::stdout().lock(),
+        )?
+    } else if args.limits.is_set() {
+        // Read stdin on its own thread so the clock can interrupt a wait.
+        let lines = ChannelLines::spawn(BufReader::new(io::stdin()));
+        play_timed(&mut game, args.limits, lines, io::stdout().lock())?
+    } else if args.tui && io::stdin().is_terminal() && io::stdout().is_terminal() {
+        // The full-screen UI needs a real terminal; anything else gets line mode.
+        play_tui(&mut game, config.colors.value)?
+    } else {
+        play(&mut game, io::stdin().lock(), io::stdout().lock())?
+    };
+
+    record_stats(config, difficulty, outcome);
+    Ok(outcome.into())
+}
+
+/// Stats are a nice-to-have: failing to record them never fails the game.
+fn record_stats(config: &Config, difficulty: Difficulty, outcome: Outcome) {
+    let Some(store) = config.stats_store() else {
+        return;
+    };
+    // Don't overwrite a file we merely failed to read.
+    let Some(mut stats) = load_stats(&store) else {
+        return;
+    };
+    stats.record(difficulty, outcome);
+    if let Err(err) = store.save(&stats) {
+        eprintln!("warning: could not save stats: {err}");
+    }
+}
+
+fn reverse(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let Some(difficulty) = difficulty(args)? else {
+        return Ok(Exit::Quit);
+    };
+    let outcome = play_reverse(difficulty.range(), io::stdin().lock(), io::stdout().lock())?;
+    Ok(match outcome {
+        ReverseOutcome::Solved { .. } => Exit::Success,
+        // The player's answers contradicted each other.
+        ReverseOutcome::Cheated { .. } => Exit::Lost,
+        ReverseOutcome::Quit => Exit::Quit,
+    })
+}
+
+fn speedrun(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let difficulty = args.difficulty.unwrap_or_default();
+    let mut game =
+        Game::with_difficulty(rng(args.seed), difficulty).with_max_attempts(args.max_attempts);
+    let lines = ChannelLines::spawn(BufReader::new(io::stdin()));
+
+    let run = timed::speedrun(
+        &mut game,
+        args.rounds.unwrap_or(5),
+        args.limits,
+        lines,
+        io::stdout().lock(),
+    )?;
+    // The run is won only if every round was.
+    Ok(run
+        .outcomes
+        .iter()
+        .map(|&outcome| Exit::from(outcome))
+        .find(|&exit| exit != Exit::Success)
+        .unwrap_or(Exit::Success))
+}
+
+fn adaptive(args: &Args, config: &Config) -> Result<Exit, Box<dyn Error>> {
+    let player = args
+        .player
+        .clone()
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_else(|| "player".to_string());
+    let target = args.target.unwrap_or(adaptive::DEFAULT_TARGET);
+    let store = config.stats_store();
+    let mut stats = match &store {
+        Some(store) => load_stats(store).ok_or("could not read stats")?,
+        None => Stats::default(),
+    };
+    let mut skill = stats.skills.get(&player).copied().unwrap_or_default();
+
+    let mut rng = rng(args.seed);
+    let mut input = io::stdin().lock();
+    let mut exit = Exit::Success;
+    for round in 1.. {
+        if args.rounds.is_some_and(|rounds| round > rounds) {
+            break;
+        }
+        if round > 1 {
+            println!();
+        }
+        let (challenge, outcome) = play_adaptive_round(
+            &mut rng,
+            &mut skill,
+            target,
+            &mut input,
+            io::stdout().lock(),
+        )?;
+        if let Outcome::Quit { .. } = outcome {
+            exit = Exit::Quit;
+            break;
+        }
+
+        // Save after every round so quitting never loses a rating.
+        stats.record(challenge.difficulty, outcome);
+        stats.skills.insert(player.clone(), skill);
+        if let Some(store) = &store {
+            if let Err(err) = store.save(&stats) {
+                eprintln!("warning: could not save stats: {err}");
+            }
+        }
+    }
+    Ok(exit)
+}
 
-        println!("You guessed {guess}");
+fn daily(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let path = data_dir()
+        .ok_or("no data directory to record the daily puzzle in")?
+        .join("daily.json");
+    let mut log = DailyLog::load(&path)?;
+    let puzzle = Puzzle::for_date(Date::today());
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too Small!"),
-            Ordering::Greater => println!("Too Big!"),
-            Ordering::Equal => {
-                println!("You Win!");
-                break;
-            },
+    match log.get(puzzle.date) {
+        Some(Some(result)) => {
+            println!("{}", message("daily.played", &[]));
+            println!();
+            println!("{}", result.share(&puzzle, args.ascii));
+            return Ok(Exit::Success);
+        }
+        Some(None) => {
+            println!("{}", message("daily.unfinished", &[]));
+            return Ok(Exit::Success);
+        }
+        None => {}
+    }
+
+    // Claim the day before playing so quitting or crashing can't buy a retry.
+    log.start(puzzle.date);
+    log.save(&path)?;
+    let result = play_daily(&puzzle, io::stdin().lock(), io::stdout().lock())?;
+    log.finish(puzzle.date, result.clone());
+    log.save(&path)?;
+
+    println!();
+    println!("{}", message("daily.share", &[]));
+    println!("{}", result.share(&puzzle, args.ascii));
+    Ok(if result.won {
+        Exit::Success
+    } else {
+        Exit::Lost
+    })
+}
+
+fn tournament(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let tournament = Tournament::new(args.players.clone(), args.difficulty.unwrap_or_default())?
+        .with_rounds(args.rounds.unwrap_or(3))
+        .with_shared_secret(args.shared_secret)
+        .with_max_attempts(args.max_attempts)
+        .with_clear_screen(io::stdout().is_terminal());
+
+    let result = play_tournament(
+        &mut rng(args.seed),
+        &tournament,
+        io::stdin().lock(),
+        io::stdout().lock(),
+    )?;
+    Ok(if result.abandoned {
+        Exit::Quit
+    } else {
+        Exit::Success
+    })
+}
+
+fn mastermind(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let defaults = Rules::default();
+    let rules = Rules::new(
+        args.code_length.unwrap_or(defaults.length()),
+        args.digits.unwrap_or(defaults.digits()),
+        args.repeats,
+    )?;
+
+    let outcome = if args.limits.is_set() {
+        play_mastermind_timed(
+            &mut rng(args.seed),
+            rules,
+            args.max_attempts,
+            args.limits,
+            ChannelLines::spawn(BufReader::new(io::stdin())),
+            io::stdout().lock(),
+        )?
+    } else {
+        play_mastermind(
+            &mut rng(args.seed),
+            rules,
+            args.max_attempts,
+            io::stdin().lock(),
+            io::stdout().lock(),
+        )?
+    };
+    Ok(match outcome {
+        CodeOutcome::Cracked { .. } => Exit::Success,
+        CodeOutcome::Lost { .. } => Exit::Lost,
+        CodeOutcome::Quit { .. } => Exit::Quit,
+    })
+}
+
+fn bench(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let difficulty = args.difficulty.unwrap_or_default();
+    let games = args.games.unwrap_or(10_000);
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    println!(
+        "{}",
+        message(
+            "bench.start",
+            &[
+                ("games", &games),
+                ("difficulty", &difficulty),
+                ("seed", &seed)
+            ]
+        )
+    );
+    println!();
+    let results = strategy::bench(&mut strategy::all_strategies(), difficulty, games, seed);
+    strategy::write_bench_report(&results, io::stdout().lock())?;
+    Ok(Exit::Success)
+}
+
+fn serve(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let difficulty = args.difficulty.unwrap_or_default();
+    let game = Game::with_difficulty(rng(args.seed), difficulty);
+    let server = Server::bind(("0.0.0.0", args.port.unwrap_or(7878)), game)?;
+
+    let address = server.local_addr()?;
+    println!(
+        "{}",
+        message(
+            "serve.start",
+            &[("difficulty", &difficulty), ("address", &address)]
+        )
+    );
+    server.run()?;
+    Ok(Exit::Success)
+}
+
+fn http(args: &Args) -> Result<Exit, Box<dyn Error>> {
+    let api = Api::new(Duration::from_secs(30 * 60));
+    let server = HttpServer::bind(("0.0.0.0", args.port.unwrap_or(8080)), api)?;
+
+    let address = server.local_addr()?;
+    println!("{}", message("serve.http", &[("address", &address)]));
+    server.run()?;
+    Ok(Exit::Success)
+}
+
+fn stats(reset: bool, config: &Config) -> Result<Exit, Box<dyn Error>> {
+    let store = config
+        .stats_store()
+        .ok_or("could not find a data directory")?;
+
+    if reset {
+        store.reset()?;
+        println!("{}", message("stats.reset", &[]));
+    } else {
+        let stats = load_stats(&store).ok_or("could not read stats")?;
+        stats.write_report(io::stdout().lock())?;
+    }
+    Ok(Exit::Success)
+}
+
+fn replay(path: &Path) -> Result<Exit, Box<dyn Error>> {
+    let recording = Recording::load(path)?;
+    let report = replay::replay(&recording)?;
+
+    match report.mismatch {
+        None => {
+            let lines = report.output.lines().count() as u32;
+            println!(
+                "{}",
+                plural("replay.matches", lines, &[("seed", &recording.seed)])
+            );
+            Ok(Exit::Success)
+        }
+        Some(mismatch) => {
+            let show = |line: Option<String>| line.unwrap_or_else(|| message("replay.end", &[]));
+            let expected = show(mismatch.expected);
+            let actual = show(mismatch.actual);
+            eprintln!("{}", message("replay.differs", &[("line", &mismatch.line)]));
+            eprintln!("  {}", message("replay.expected", &[("text", &expected)]));
+            eprintln!("  {}", message("replay.actual", &[("text", &actual)]));
+            Ok(Exit::Lost)
+        }
+    }
+}
+
+/// Loads stats, starting fresh when the file is missing or corrupt and
+/// giving up (`None`) when it could not be read at all.
+fn load_stats(store: &StatsStore) -> Option<Stats> {
+    match store.load() {
+        Ok(stats) => Some(stats),
+        Err(err @ StatsError::Corrupt { .. }) => {
+            eprintln!("warning: {err}; starting fresh");
+            Some(Stats::default())
+        }
+        Err(err) => {
+            eprintln!("warning: {err}");
+            None
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,439 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Instant;
+
+use crate::i18n::{message, plural};
+use crate::input::{reserved_word, Command, Line, LineSource};
+use crate::timed::TimeLimits;
+
+/// The shape of a code: how many positions, how many distinct digits
+/// (`0` up to `digits - 1`) and whether a digit may appear twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    length: usize,
+    digits: u8,
+    repeats: bool,
+}
+
+/// Hints enumerate every possible code, so they are only offered when there
+/// are at most this many.
+pub const MAX_HINT_CODES: usize = 10_000;
+
+impl Rules {
+    pub fn new(length: usize, digits: u8, repeats: bool) -> Result<Rules, String> {
+        if !(1..=8).contains(&length) {
+            return Err(format!("code length must be between 1 and 8, not {length}"));
+        }
+        if !(2..=10).contains(&digits) {
+            return Err(format!("digits must be between 2 and 10, not {digits}"));
+        }
+        if !repeats && length > usize::from(digits) {
+            return Err(format!(
+                "a {length}-digit code needs repeats with only {digits} digits"
+            ));
+        }
+        Ok(Rules {
+            length,
+            digits,
+            repeats,
+        })
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn digits(&self) -> u8 {
+        self.digits
+    }
+
+    pub fn repeats(&self) -> bool {
+        self.repeats
+    }
+
+    /// How many different secrets these rules allow.
+    pub fn code_count(&self) -> u64 {
+        let digits = u64::from(self.digits);
+        if self.repeats {
+            digits.pow(self.length as u32)
+        } else {
+            (0..self.length as u64).map(|i| digits - i).product()
+        }
+    }
+
+    pub fn random_code<R: Rng>(&self, rng: &mut R) -> Code {
+        let digits: Vec<u8> = (0..self.digits).collect();
+        if self.repeats {
+            Code(
+                (0..self.length)
+                    .map(|_| *digits.choose(rng).unwrap())
+                    .collect(),
+            )
+        } else {
+            Code(digits.choose_multiple(rng, self.length).copied().collect())
+        }
+    }
+
+    /// Parses a guess such as `0123` or `0 1 2 3`.
+    pub fn parse(&self, text: &str) -> Result<Code, String> {
+        let digits: Vec<u8> = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| match c.to_digit(10) {
+                Some(d) if d < u32::from(self.digits) => Ok(d as u8),
+                _ => Err(message(
+                    "mastermind.not-a-digit",
+                    &[("digit", &c), ("max", &(self.digits - 1))],
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        if digits.len() != self.length {
+            return Err(plural("mastermind.wrong-length", self.length as u32, &[]));
+        }
+        if !self.repeats && (1..digits.len()).any(|i| digits[i..].contains(&digits[i - 1])) {
+            return Err(message("mastermind.repeated", &[]));
+        }
+        Ok(Code(digits))
+    }
+
+    /// Every code these rules allow, in ascending order.
+    pub fn all_codes(&self) -> Vec<Code> {
+        let mut codes: Vec<Vec<u8>> = vec![Vec::new()];
+        for _ in 0..self.length {
+            let mut longer = Vec::new();
+            for code in &codes {
+                for digit in 0..self.digits {
+                    if self.repeats || !code.contains(&digit) {
+                        let mut next = code.clone();
+                        next.push(digit);
+                        longer.push(next);
+                    }
+                }
+            }
+            codes = longer;
+        }
+        codes.into_iter().map(Code).collect()
+    }
+}
+
+impl Default for Rules {
+    /// Classic bulls and cows: four different digits from 0 to 9.
+    fn default() -> Self {
+        Rules {
+            length: 4,
+            digits: 10,
+            repeats: false,
+        }
+    }
+}
+
+/// A secret or a guess: one digit per position.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Code(pub Vec<u8>);
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for digit in &self.0 {
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The answer to a guess: digits in the right position (bulls) and right
+/// digits in the wrong position (cows).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub exact: usize,
+    pub misplaced: usize,
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = message(
+            "mastermind.score",
+            &[("exact", &self.exact), ("misplaced", &self.misplaced)],
+        );
+        f.write_str(&text)
+    }
+}
+
+pub fn score(secret: &Code, guess: &Code) -> Score {
+    let exact = secret
+        .0
+        .iter()
+        .zip(&guess.0)
+        .filter(|(s, g)| s == g)
+        .count();
+
+    let mut secret_counts = [0usize; 10];
+    let mut guess_counts = [0usize; 10];
+    for (&s, &g) in secret.0.iter().zip(&guess.0) {
+        secret_counts[usize::from(s)] += 1;
+        guess_counts[usize::from(g)] += 1;
+    }
+    let common: usize = secret_counts
+        .iter()
+        .zip(guess_counts)
+        .map(|(&s, g)| s.min(g))
+        .sum();
+
+    Score {
+        exact,
+        misplaced: common - exact,
+    }
+}
+
+/// Knuth's minimax solver: keeps every code consistent with the answers so
+/// far and suggests the guess whose worst-case answer leaves the fewest.
+pub struct Solver {
+    length: usize,
+    all: Vec<Code>,
+    /// Kept in ascending order, like `all`.
+    candidates: Vec<Code>,
+}
+
+impl Solver {
+    /// `None` when the rules allow more than [`MAX_HINT_CODES`] codes.
+    pub fn new(rules: Rules) -> Option<Solver> {
+        if rules.code_count() > MAX_HINT_CODES as u64 {
+            return None;
+        }
+        let all = rules.all_codes();
+        Some(Solver {
+            length: rules.length(),
+            candidates: all.clone(),
+            all,
+        })
+    }
+
+    /// Codes still consistent with every answer.
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    pub fn record(&mut self, guess: &Code, answer: Score) {
+        self.candidates
+            .retain(|candidate| score(candidate, guess) == answer);
+    }
+
+    /// The next guess to make. Ties prefer codes that could still be the
+    /// secret, then the lowest code, so hints are deterministic.
+    pub fn hint(&self) -> Option<Code> {
+        match self.candidates.len() {
+            0 => return None,
+            1 | 2 => return self.candidates.first().cloned(),
+            _ => {}
+        }
+
+        // Before any answer every code is alike up to renaming digits, so
+        // only the lowest code of each shape (0123, 0012, 0101, ...) needs
+        // scoring. This keeps the first hint fast.
+        let pool: Vec<&Code> = if self.candidates.len() == self.all.len() {
+            self.all.iter().filter(|code| is_canonical(code)).collect()
+        } else {
+            self.all.iter().collect()
+        };
+
+        let mut partitions = vec![0usize; (self.length + 1) * (self.length + 1)];
+        let mut best: Option<(usize, bool, &Code)> = None;
+        for guess in pool {
+            partitions.fill(0);
+            for candidate in &self.candidates {
+                let answer = score(candidate, guess);
+                partitions[answer.exact * (self.length + 1) + answer.misplaced] += 1;
+            }
+            let worst = partitions.iter().copied().max().unwrap_or(0);
+            let is_candidate = self.candidates.binary_search(guess).is_ok();
+
+            let better = match best {
+                None => true,
+                Some((best_worst, best_is_candidate, _)) => {
+                    worst < best_worst
+                        || (worst == best_worst && is_candidate && !best_is_candidate)
+                }
+            };
+            if better {
+                best = Some((worst, is_candidate, guess));
+            }
+        }
+        best.map(|(_, _, guess)| guess.clone())
+    }
+}
+
+/// Whether each new digit in `code` is the next unused one counting from 0,
+/// making it the lowest code among those that differ only by renaming digits.
+fn is_canonical(code: &Code) -> bool {
+    let mut next = 0;
+    for &digit in &code.0 {
+        if digit == next {
+            next += 1;
+        } else if digit > next {
+            return false;
+        }
+    }
+    true
+}
+
+/// How a code-breaking game ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeOutcome {
+    Cracked { attempts: u32, hints: u32 },
+    Lost { attempts: u32, secret: Code },
+    Quit { attempts: u32 },
+}
+
+/// Plays code-breaking: guess the secret code, type `hint` for a solver
+/// suggestion. `quit`, `giveup` and `history` work as at the number prompt.
+pub fn play_mastermind<R: Rng, I: LineSource, O: Write>(
+    rng: &mut R,
+    rules: Rules,
+    max_attempts: Option<u32>,
+    input: I,
+    output: O,
+) -> io::Result<CodeOutcome> {
+    play_mastermind_timed(
+        rng,
+        rules,
+        max_attempts,
+        TimeLimits::default(),
+        input,
+        output,
+    )
+}
+
+/// [`play_mastermind`] against the clock, with the same limits as
+/// [`crate::timed::play_timed`]. Running out of time loses the game.
+pub fn play_mastermind_timed<R: Rng, I: LineSource, O: Write>(
+    rng: &mut R,
+    rules: Rules,
+    max_attempts: Option<u32>,
+    limits: TimeLimits,
+    mut input: I,
+    mut output: O,
+) -> io::Result<CodeOutcome> {
+    let secret = rules.random_code(rng);
+    let mut solver = Solver::new(rules);
+    let mut attempts = 0;
+    let mut hints = 0;
+    let mut history: Vec<(Code, Score)> = Vec::new();
+    let started = Instant::now();
+    // Commands and bad codes don't buy more time for the current guess.
+    let mut guess_started = started;
+
+    let intro = if rules.repeats() {
+        "mastermind.intro.repeats"
+    } else {
+        "mastermind.intro.unique"
+    };
+    writeln!(
+        output,
+        "{}",
+        message(
+            intro,
+            &[("length", &rules.length()), ("max", &(rules.digits() - 1))]
+        )
+    )?;
+
+    loop {
+        writeln!(output, "{}", message("mastermind.prompt", &[]))?;
+
+        let line = match limits.timeout(started.elapsed(), guess_started.elapsed()) {
+            Some(timeout) if timeout.is_zero() => Line::TimedOut,
+            timeout => input.next_line(timeout)?,
+        };
+        let line = match line {
+            Line::Text(line) => line,
+            Line::Eof => {
+                writeln!(
+                    output,
+                    "{}",
+                    message("mastermind.no-more-input", &[("secret", &secret)])
+                )?;
+                return Ok(CodeOutcome::Quit { attempts });
+            }
+            Line::TimedOut => {
+                writeln!(
+                    output,
+                    "{}",
+                    message("mastermind.times-up", &[("secret", &secret)])
+                )?;
+                return Ok(CodeOutcome::Lost { attempts, secret });
+            }
+        };
+
+        match reserved_word(&line) {
+            Some(Command::Hint) => {
+                match solver.as_ref().and_then(Solver::hint) {
+                    Some(hint) => {
+                        hints += 1;
+                        writeln!(output, "{}", message("mastermind.try", &[("code", &hint)]))?;
+                    }
+                    None => writeln!(output, "{}", message("mastermind.no-hint", &[]))?,
+                }
+                continue;
+            }
+            Some(Command::History) => {
+                if history.is_empty() {
+                    writeln!(output, "{}", message("game.no-history", &[]))?;
+                }
+                for (guess, answer) in &history {
+                    writeln!(output, "  {guess}: {answer}")?;
+                }
+                continue;
+            }
+            Some(Command::GiveUp) => {
+                writeln!(
+                    output,
+                    "{}",
+                    message("mastermind.gave-up", &[("secret", &secret)])
+                )?;
+                return Ok(CodeOutcome::Lost { attempts, secret });
+            }
+            Some(Command::Quit) => {
+                writeln!(output, "{}", message("game.quit", &[]))?;
+                return Ok(CodeOutcome::Quit { attempts });
+            }
+            // There is no range to show for a code; treat it as a guess.
+            Some(Command::Range | Command::Undo | Command::Guess(_)) | None => {}
+        }
+
+        let guess = match rules.parse(&line) {
+            Ok(guess) => guess,
+            Err(message) => {
+                writeln!(output, "{message}")?;
+                continue;
+            }
+        };
+
+        attempts += 1;
+        guess_started = Instant::now();
+        let answer = score(&secret, &guess);
+        if let Some(solver) = solver.as_mut() {
+            solver.record(&guess, answer);
+        }
+
+        if answer.exact == rules.length() {
+            writeln!(
+                output,
+                "{}",
+                plural("mastermind.won", attempts, &[("secret", &secret)])
+            )?;
+            return Ok(CodeOutcome::Cracked { attempts, hints });
+        }
+        writeln!(output, "{answer}")?;
+        history.push((guess, answer));
+
+        if max_attempts == Some(attempts) {
+            writeln!(
+                output,
+                "{}",
+                message("mastermind.out-of-attempts", &[("secret", &secret)])
+            )?;
+            return Ok(CodeOutcome::Lost { attempts, secret });
+        }
+    }
+}
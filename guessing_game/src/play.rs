@@ -0,0 +1,356 @@
+use rand::Rng;
+use std::cmp::Ordering;
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome, HINT_PENALTY};
+use crate::i18n::{message, plural};
+use crate::input::{parse_command, Command, Line, LineSource};
+use crate::timed::TimeLimits;
+
+/// Runs the guessing loop against any input and output handles until the
+/// secret number is found or the attempts run out.
+///
+/// `main` passes locked stdin/stdout; tests pass byte slices and a `Vec<u8>`
+/// so whole sessions can be scripted and their transcripts checked.
+pub fn play<R: Rng, I: LineSource, O: Write>(
+    game: &mut Game<R>,
+    mut input: I,
+    output: O,
+) -> io::Result<Outcome> {
+    play_loop(
+        game,
+        &mut input,
+        output,
+        direction,
+        &TimeLimits::default(),
+        true,
+    )
+}
+
+/// The classic reply: which way the secret lies.
+pub(crate) fn direction(_guess: i64, ordering: Ordering) -> String {
+    let key = match ordering {
+        Ordering::Less => "game.too-small",
+        Ordering::Greater => "game.too-big",
+        Ordering::Equal => "game.you-win",
+    };
+    message(key, &[])
+}
+
+/// The loop behind [`play`], with the line printed after each guess left to
+/// `feedback`, which gets the guess and how it compared to the secret.
+/// Variants such as hot/cold use this to change what a guess tells you
+/// without changing the rules. Since such feedback need not say which way
+/// the secret lies, `hint` and `range` only use what hints have revealed.
+pub fn play_with_feedback<R, I, O, F>(
+    game: &mut Game<R>,
+    mut input: I,
+    output: O,
+    feedback: F,
+) -> io::Result<Outcome>
+where
+    R: Rng,
+    I: LineSource,
+    O: Write,
+    F: FnMut(i64, Ordering) -> String,
+{
+    play_loop(
+        game,
+        &mut input,
+        output,
+        feedback,
+        &TimeLimits::default(),
+        false,
+    )
+}
+
+/// What [`play_loop`] says about each guess. Any `FnMut(i64, Ordering) ->
+/// String` will do; replies that depend on earlier guesses also need to
+/// hear when one is taken back.
+pub(crate) trait Replies {
+    fn reply(&mut self, guess: i64, ordering: Ordering) -> String;
+
+    /// The last guess was taken back with `undo`.
+    fn undo(&mut self) {}
+}
+
+impl<F: FnMut(i64, Ordering) -> String> Replies for F {
+    fn reply(&mut self, guess: i64, ordering: Ordering) -> String {
+        self(guess, ordering)
+    }
+}
+
+/// The one game loop every line-mode variant shares. With time limits set
+/// it also reports the elapsed time and ends the game when time runs out.
+/// `directional` says whether `feedback` tells the player which way the
+/// secret lies, so `hint` and `range` may narrow by the guesses too.
+pub(crate) fn play_loop<R, I, O, F>(
+    game: &mut Game<R>,
+    input: &mut I,
+    mut output: O,
+    mut feedback: F,
+    limits: &TimeLimits,
+    directional: bool,
+) -> io::Result<Outcome>
+where
+    R: Rng,
+    I: LineSource + ?Sized,
+    O: Write,
+    F: Replies,
+{
+    let range = game.range();
+    let started = Instant::now();
+    // Commands and bad input don't buy more time for the current guess.
+    let mut guess_started = started;
+    // What each guess was told, for `history`.
+    let mut replies: Vec<(i64, String)> = Vec::new();
+    let known = |game: &Game<R>| {
+        if directional {
+            game.possible_range()
+        } else {
+            game.hinted_range()
+        }
+    };
+
+    writeln!(output, "{}", message("game.intro", &[]))?;
+    writeln!(
+        output,
+        "{}",
+        message(
+            "game.range",
+            &[("min", range.start()), ("max", range.end())]
+        )
+    )?;
+    if let Some(max) = game.max_attempts() {
+        writeln!(output, "{}", plural("game.you-have", max, &[]))?;
+    }
+
+    loop {
+        writeln!(output, "{}", message("game.prompt", &[]))?;
+
+        let line = match limits.timeout(started.elapsed(), guess_started.elapsed()) {
+            Some(timeout) if timeout.is_zero() => Line::TimedOut,
+            timeout => input.next_line(timeout)?,
+        };
+        let command = match line {
+            Line::Text(text) => parse_command(&text, &range),
+            Line::Eof => {
+                let secret = game.secret_number();
+                writeln!(
+                    output,
+                    "{}",
+                    message("game.no-more-input", &[("secret", &secret)])
+                )?;
+                return Ok(Outcome::Quit {
+                    attempts: game.attempts(),
+                });
+            }
+            Line::TimedOut => {
+                let secret = game.secret_number();
+                writeln!(
+                    output,
+                    "{}",
+                    message("game.times-up", &[("secret", &secret)])
+                )?;
+                return Ok(Outcome::Lost {
+                    attempts: game.attempts(),
+                    secret_number: game.secret_number(),
+                });
+            }
+        };
+        let guess = match command {
+            Ok(Command::Guess(guess)) => guess,
+            Ok(Command::Hint) => {
+                match game.hint(known(game)) {
+                    Some(hinted) => writeln!(
+                        output,
+                        "{}",
+                        message(
+                            "game.hint",
+                            &[
+                                ("min", hinted.start()),
+                                ("max", hinted.end()),
+                                ("penalty", &HINT_PENALTY)
+                            ]
+                        )
+                    )?,
+                    None => writeln!(output, "{}", message("game.no-hint", &[]))?,
+                }
+                continue;
+            }
+            Ok(Command::Range) => {
+                let known = known(game);
+                writeln!(
+                    output,
+                    "{}",
+                    message(
+                        "game.range-now",
+                        &[("min", known.start()), ("max", known.end())]
+                    )
+                )?;
+                continue;
+            }
+            Ok(Command::History) => {
+                if replies.is_empty() {
+                    writeln!(output, "{}", message("game.no-history", &[]))?;
+                }
+                for (guess, reply) in &replies {
+                    writeln!(output, "  {guess}: {reply}")?;
+                }
+                continue;
+            }
+            Ok(Command::Undo) => {
+                match game.undo() {
+                    Some(guess) => {
+                        replies.pop();
+                        feedback.undo();
+                        writeln!(output, "{}", message("game.undone", &[("guess", &guess)]))?;
+                    }
+                    None => writeln!(output, "{}", message("game.no-undo", &[]))?,
+                }
+                continue;
+            }
+            Ok(Command::GiveUp) => {
+                let secret = game.secret_number();
+                writeln!(
+                    output,
+                    "{}",
+                    message("game.gave-up", &[("secret", &secret)])
+                )?;
+                return Ok(Outcome::Lost {
+                    attempts: game.attempts(),
+                    secret_number: secret,
+                });
+            }
+            Ok(Command::Quit) => {
+                writeln!(output, "{}", message("game.quit", &[]))?;
+                return Ok(Outcome::Quit {
+                    attempts: game.attempts(),
+                });
+            }
+            Err(err) => {
+                writeln!(output, "{err}")?;
+                continue;
+            }
+        };
+
+        writeln!(
+            output,
+            "{}",
+            message("game.you-guessed", &[("guess", &guess)])
+        )?;
+
+        let turn = game.take_turn(guess);
+        guess_started = Instant::now();
+        let reply = feedback.reply(guess, turn.ordering);
+        writeln!(output, "{reply}")?;
+        replies.push((guess, reply));
+        if limits.is_set() {
+            let seconds = format!("{:.1}", started.elapsed().as_secs_f64());
+            writeln!(
+                output,
+                "{}",
+                message("game.elapsed", &[("seconds", &seconds)])
+            )?;
+        }
+
+        match turn.outcome {
+            Some(
+                outcome @ Outcome::Won {
+                    attempts: used,
+                    score,
+                },
+            ) => {
+                let optimal = game.difficulty().optimal_guesses();
+                writeln!(
+                    output,
+                    "{}",
+                    plural(
+                        "game.won",
+                        used,
+                        &[("optimal", &optimal), ("score", &score)]
+                    )
+                )?;
+                return Ok(outcome);
+            }
+            Some(outcome) => {
+                let secret = game.secret_number();
+                writeln!(
+                    output,
+                    "{}",
+                    message("game.out-of-attempts", &[("secret", &secret)])
+                )?;
+                return Ok(outcome);
+            }
+            None => {
+                if let Some(left) = game.attempts_left() {
+                    writeln!(output, "{}", plural("game.attempts-left", left, &[]))?;
+                }
+            }
+        }
+    }
+}
+
+/// Asks the player to pick a difficulty, re-prompting until the answer is
+/// valid. An empty answer picks the normal difficulty; closed input gives
+/// `None`.
+pub fn choose_difficulty<I: BufRead, O: Write>(
+    mut input: I,
+    mut output: O,
+) -> io::Result<Option<Difficulty>> {
+    loop {
+        writeln!(output, "{}", message("difficulty.prompt", &[]))?;
+
+        let Some(answer) = read_answer(&mut input)? else {
+            return Ok(None);
+        };
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Ok(Some(Difficulty::Normal));
+        }
+        if !answer.eq_ignore_ascii_case("custom") {
+            match answer.parse() {
+                Ok(difficulty) => return Ok(Some(difficulty)),
+                Err(_) => {
+                    writeln!(
+                        output,
+                        "{}",
+                        message("difficulty.unknown", &[("name", &answer)])
+                    )?;
+                    continue;
+                }
+            }
+        }
+
+        writeln!(output, "{}", message("difficulty.lowest", &[]))?;
+        let Some(min) = read_answer(&mut input)? else {
+            return Ok(None);
+        };
+        writeln!(output, "{}", message("difficulty.highest", &[]))?;
+        let Some(max) = read_answer(&mut input)? else {
+            return Ok(None);
+        };
+
+        match (min.trim().parse(), max.trim().parse()) {
+            (Ok(min), Ok(max)) => match Difficulty::custom(min, max) {
+                Ok(difficulty) => return Ok(Some(difficulty)),
+                Err(_) => writeln!(
+                    output,
+                    "{}",
+                    message("difficulty.bad-order", &[("min", &min), ("max", &max)])
+                )?,
+            },
+            _ => writeln!(output, "{}", message("difficulty.not-numbers", &[]))?,
+        }
+    }
+}
+
+fn read_answer<I: BufRead>(input: &mut I) -> io::Result<Option<String>> {
+    let mut answer = String::new();
+    if input.read_line(&mut answer)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(answer))
+}
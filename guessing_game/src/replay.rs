@@ -0,0 +1,276 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Instant;
+
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome};
+use crate::hotcold::{play_hot_cold, Thresholds};
+use crate::i18n::{self, Locale};
+use crate::input::{Line, LineSource};
+use crate::play::play;
+
+/// Bumped whenever the file layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Everything that decides how a session plays out, besides the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    pub difficulty: Difficulty,
+    pub max_attempts: Option<u32>,
+    /// Hot/cold replies with these bands, or the classic ones when `None`.
+    pub hot_cold: Option<Thresholds>,
+    /// The language the game spoke; older recordings are English.
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stream {
+    Input,
+    Output,
+}
+
+/// A line typed by the player or text printed by the game, stamped with
+/// milliseconds since the session started.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub at_ms: u64,
+    pub stream: Stream,
+    pub text: String,
+}
+
+/// A whole session, enough to play it again exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recording {
+    pub version: u32,
+    pub seed: u64,
+    pub settings: Settings,
+    pub events: Vec<Event>,
+}
+
+impl Recording {
+    /// Everything the player typed, raw, in order.
+    pub fn input(&self) -> String {
+        self.text(Stream::Input)
+    }
+
+    /// Everything the game printed, in order.
+    pub fn output(&self) -> String {
+        self.text(Stream::Output)
+    }
+
+    fn text(&self, stream: Stream) -> String {
+        self.events
+            .iter()
+            .filter(|event| event.stream == stream)
+            .map(|event| event.text.as_str())
+            .collect()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::from)?;
+        fs::write(path, json)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Recording> {
+        let recording: Recording = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if recording.version != FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "recording format {} is not supported (expected {FORMAT_VERSION})",
+                    recording.version
+                ),
+            ));
+        }
+        Ok(recording)
+    }
+}
+
+/// Plays one session from a seed and settings, in the settings' language.
+/// Recording and replaying both go through here so they cannot drift apart.
+pub fn play_session<I: LineSource, O: Write>(
+    seed: u64,
+    settings: Settings,
+    input: I,
+    output: O,
+) -> io::Result<Outcome> {
+    let previous = i18n::locale();
+    i18n::set_locale(settings.locale);
+    let outcome = play_settings(seed, settings, input, output);
+    i18n::set_locale(previous);
+    outcome
+}
+
+fn play_settings<I: LineSource, O: Write>(
+    seed: u64,
+    settings: Settings,
+    input: I,
+    output: O,
+) -> io::Result<Outcome> {
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(seed), settings.difficulty)
+        .with_max_attempts(settings.max_attempts);
+
+    match settings.hot_cold {
+        Some(thresholds) => play_hot_cold(&mut game, thresholds, input, output),
+        None => play(&mut game, input, output),
+    }
+}
+
+type Events = Rc<RefCell<Vec<Event>>>;
+
+fn log(events: &Events, started: Instant, stream: Stream, text: &str) {
+    let mut events = events.borrow_mut();
+    // Output arrives in fragments; keep consecutive ones as one event.
+    if let Some(last) = events.last_mut() {
+        if last.stream == stream && stream == Stream::Output {
+            last.text.push_str(text);
+            return;
+        }
+    }
+    events.push(Event {
+        at_ms: started.elapsed().as_millis() as u64,
+        stream,
+        text: text.to_string(),
+    });
+}
+
+struct RecordedInput<I> {
+    inner: I,
+    events: Events,
+    started: Instant,
+}
+
+impl<I: LineSource> LineSource for RecordedInput<I> {
+    fn next_line(&mut self, timeout: Option<std::time::Duration>) -> io::Result<Line> {
+        let line = self.inner.next_line(timeout)?;
+        if let Line::Text(text) = &line {
+            log(&self.events, self.started, Stream::Input, text);
+        }
+        Ok(line)
+    }
+}
+
+struct RecordedOutput<O> {
+    inner: O,
+    events: Events,
+    started: Instant,
+}
+
+impl<O: Write> Write for RecordedOutput<O> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        log(
+            &self.events,
+            self.started,
+            Stream::Output,
+            &String::from_utf8_lossy(&buf[..written]),
+        );
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Plays a session as normal while capturing it into a [`Recording`].
+pub fn record<I: LineSource, O: Write>(
+    seed: u64,
+    settings: Settings,
+    input: I,
+    output: O,
+) -> io::Result<(Outcome, Recording)> {
+    let events: Events = Rc::default();
+    let started = Instant::now();
+
+    let outcome = play_session(
+        seed,
+        settings,
+        RecordedInput {
+            inner: input,
+            events: Rc::clone(&events),
+            started,
+        },
+        RecordedOutput {
+            inner: output,
+            events: Rc::clone(&events),
+            started,
+        },
+    )?;
+
+    let recording = Recording {
+        version: FORMAT_VERSION,
+        seed,
+        settings,
+        events: events.take(),
+    };
+    Ok((outcome, recording))
+}
+
+/// The first line where a replay's output differs from the recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// 1-based.
+    pub line: usize,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+/// What happened when a recording was played again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    pub outcome: Outcome,
+    pub output: String,
+    pub mismatch: Option<Mismatch>,
+}
+
+/// Re-runs a recording with the same seed, settings and input, and checks
+/// the game prints exactly what it printed the first time.
+pub fn replay(recording: &Recording) -> io::Result<ReplayReport> {
+    let input = recording.input();
+    let mut output = Vec::new();
+    let outcome = play_session(
+        recording.seed,
+        recording.settings,
+        input.as_bytes(),
+        &mut output,
+    )?;
+    let output = String::from_utf8_lossy(&output).into_owned();
+
+    let expected = recording.output();
+    let mismatch = first_difference(&expected, &output);
+    Ok(ReplayReport {
+        outcome,
+        output,
+        mismatch,
+    })
+}
+
+fn first_difference(expected: &str, actual: &str) -> Option<Mismatch> {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 0;
+    loop {
+        line += 1;
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return None,
+            (e, a) if e == a => continue,
+            (e, a) => {
+                return Some(Mismatch {
+                    line,
+                    expected: e.map(String::from),
+                    actual: a.map(String::from),
+                })
+            }
+        }
+    }
+}
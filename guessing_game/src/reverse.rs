@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::RangeInclusive;
+
+use crate::i18n::{message, plural};
+
+/// The program's side of a reverse game: it guesses the player's number by
+/// bisecting the interval the player's answers still allow.
+#[derive(Debug, Clone, Default)]
+pub struct Guesser {
+    low: i64,
+    high: i64,
+    steps: u32,
+}
+
+/// The player's answers contradict each other: no number fits them all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cheating;
+
+impl fmt::Display for Cheating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&message("reverse.no-fit", &[]))
+    }
+}
+
+impl Guesser {
+    pub fn new(range: RangeInclusive<i64>) -> Self {
+        Self {
+            low: *range.start(),
+            high: *range.end(),
+            steps: 0,
+        }
+    }
+
+    /// The midpoint of the numbers still possible.
+    pub fn next_guess(&self) -> i64 {
+        (self.low as i128 + (self.high as i128 - self.low as i128) / 2) as i64
+    }
+
+    /// Guesses asked so far.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// The numbers the player's answers still allow.
+    pub fn remaining(&self) -> RangeInclusive<i64> {
+        self.low..=self.high
+    }
+
+    /// Narrows the interval using how the last guess compared to the
+    /// player's number, the same `Ordering` the normal game produces.
+    pub fn feedback(&mut self, ordering: Ordering) -> Result<(), Cheating> {
+        self.narrow(self.next_guess(), ordering)
+    }
+
+    /// Like [`Guesser::feedback`], but for any guess inside the interval,
+    /// not just the midpoint.
+    pub fn narrow(&mut self, guess: i64, ordering: Ordering) -> Result<(), Cheating> {
+        self.steps += 1;
+
+        match ordering {
+            Ordering::Equal if self.remaining().contains(&guess) => Ok(()),
+            Ordering::Less if guess < self.high => {
+                self.low = self.low.max(guess + 1);
+                Ok(())
+            }
+            Ordering::Greater if guess > self.low => {
+                self.high = self.high.min(guess - 1);
+                Ok(())
+            }
+            _ => Err(Cheating),
+        }
+    }
+}
+
+/// How a reverse game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReverseOutcome {
+    Solved { number: i64, steps: u32 },
+    Cheated { steps: u32 },
+    Quit,
+}
+
+/// Plays a reverse game where the player thinks of a number in `range` and
+/// answers each of the program's guesses with higher, lower or correct.
+pub fn play_reverse<I: BufRead, O: Write>(
+    range: RangeInclusive<i64>,
+    mut input: I,
+    mut output: O,
+) -> io::Result<ReverseOutcome> {
+    writeln!(
+        output,
+        "{}",
+        message(
+            "reverse.intro",
+            &[("min", range.start()), ("max", range.end())]
+        )
+    )?;
+    let mut guesser = Guesser::new(range);
+
+    loop {
+        let guess = guesser.next_guess();
+        writeln!(output, "{}", message("reverse.ask", &[("guess", &guess)]))?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(ReverseOutcome::Quit);
+        }
+        let Some(ordering) = parse_answer(&answer) else {
+            writeln!(output, "{}", message("reverse.unclear", &[]))?;
+            continue;
+        };
+
+        if let Err(err) = guesser.feedback(ordering) {
+            writeln!(
+                output,
+                "{}",
+                message("reverse.cheating", &[("reason", &err)])
+            )?;
+            return Ok(ReverseOutcome::Cheated {
+                steps: guesser.steps(),
+            });
+        }
+        if ordering == Ordering::Equal {
+            let steps = guesser.steps();
+            writeln!(
+                output,
+                "{}",
+                plural("reverse.solved", steps, &[("guess", &guess)])
+            )?;
+            return Ok(ReverseOutcome::Solved {
+                number: guess,
+                steps,
+            });
+        }
+    }
+}
+
+/// Maps the player's answer to how the guess compares to their number:
+/// "higher" means the guess was too small.
+pub fn parse_answer(answer: &str) -> Option<Ordering> {
+    match answer.trim().to_lowercase().as_str() {
+        "higher" | "h" | "+" => Some(Ordering::Less),
+        "lower" | "l" | "-" => Some(Ordering::Greater),
+        "correct" | "c" | "yes" | "y" | "=" => Some(Ordering::Equal),
+        _ => None,
+    }
+}
@@ -0,0 +1,232 @@
+use rand::rngs::StdRng;
+use std::cmp::Ordering;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::game::Game;
+use crate::input::parse_guess;
+
+// Line protocol, one command or reply per line:
+//
+//   server -> client   WELCOME <id> <min> <max>   on connect
+//   client -> server   NAME <name>                reply: OK
+//   client -> server   GUESS <n>                  reply: LOW | HIGH | WIN
+//   server -> all      WINNER <name> <secret>     when someone wins
+//   server -> all      ROUND <n> <min> <max>      a new secret was picked
+//   client -> server   QUIT                       reply: BYE
+//   server -> client   ERR <message>              for anything malformed
+
+/// How long a write to one player may block before they are dropped. A win
+/// is announced while the round is locked, so that everyone sees the rounds
+/// change in order; a client that stops reading can hold up every other
+/// player for up to this long, once, before it is dropped.
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+
+struct Player {
+    id: u32,
+    name: String,
+    stream: TcpStream,
+}
+
+/// State shared by every connection: the current secret and who to tell
+/// when it is found.
+struct Round {
+    game: Game<StdRng>,
+    number: u32,
+    players: Vec<Player>,
+    next_id: u32,
+}
+
+impl Round {
+    fn broadcast(&mut self, line: &str) {
+        // A player whose connection fails or times out is dropped from the
+        // round, and disconnected so their own thread ends too.
+        self.players.retain_mut(|player| {
+            let sent = writeln!(player.stream, "{line}").is_ok();
+            if !sent {
+                let _ = player.stream.shutdown(Shutdown::Both);
+            }
+            sent
+        });
+    }
+
+    fn name_of(&self, id: u32) -> String {
+        self.players
+            .iter()
+            .find(|player| player.id == id)
+            .map(|player| player.name.clone())
+            .unwrap_or_else(|| format!("player{id}"))
+    }
+}
+
+/// A LAN server where everyone connected races to guess the same number.
+pub struct Server {
+    listener: TcpListener,
+    round: Arc<Mutex<Round>>,
+}
+
+impl Server {
+    pub fn bind<A: ToSocketAddrs>(addr: A, game: Game<StdRng>) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            round: Arc::new(Mutex::new(Round {
+                game,
+                number: 1,
+                players: Vec::new(),
+                next_id: 1,
+            })),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts players forever, one thread per connection.
+    pub fn run(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let round = Arc::clone(&self.round);
+            thread::spawn(move || {
+                // A broken connection only ends that player's session.
+                let _ = handle_client(stream, round);
+            });
+        }
+        Ok(())
+    }
+}
+
+fn handle_client(stream: TcpStream, round: Arc<Mutex<Round>>) -> io::Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream.try_clone()?);
+
+    let id = {
+        let mut round = round.lock().unwrap();
+        let id = round.next_id;
+        round.next_id += 1;
+        let range = round.game.range();
+        writeln!(writer, "WELCOME {id} {} {}", range.start(), range.end())?;
+        round.players.push(Player {
+            id,
+            name: format!("player{id}"),
+            stream,
+        });
+        id
+    };
+
+    let result = play(id, reader, &mut writer, &round);
+    // However the session ended, the player leaves the round.
+    round
+        .lock()
+        .unwrap()
+        .players
+        .retain(|player| player.id != id);
+    result
+}
+
+fn play(
+    id: u32,
+    reader: BufReader<TcpStream>,
+    writer: &mut TcpStream,
+    round: &Mutex<Round>,
+) -> io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        let (command, argument) = match line.trim().split_once(' ') {
+            Some((command, argument)) => (command.to_uppercase(), argument.trim()),
+            None => (line.trim().to_uppercase(), ""),
+        };
+
+        // Ordinary replies are written after the lock is released, so a slow
+        // reader only holds up itself. A win is the exception: WIN and the
+        // broadcasts go out under the lock, which can stall the round for up
+        // to WRITE_TIMEOUT per stalled player.
+        let reply = {
+            let mut round = round.lock().unwrap();
+            match command.as_str() {
+                "NAME" if !argument.is_empty() && !argument.contains(char::is_whitespace) => {
+                    if let Some(player) = round.players.iter_mut().find(|p| p.id == id) {
+                        player.name = argument.to_string();
+                    }
+                    "OK".to_string()
+                }
+                "NAME" => "ERR name must be a single word".to_string(),
+                "GUESS" => {
+                    let range = round.game.range();
+                    match parse_guess(argument, &range) {
+                        Err(err) => format!("ERR {err}"),
+                        Ok(guess) => match round.game.guess(guess) {
+                            Ordering::Less => "LOW".to_string(),
+                            Ordering::Greater => "HIGH".to_string(),
+                            Ordering::Equal => {
+                                // The winner hears WIN before the broadcasts.
+                                writeln!(writer, "WIN")?;
+                                let winner = round.name_of(id);
+                                let secret = round.game.secret_number();
+                                round.broadcast(&format!("WINNER {winner} {secret}"));
+
+                                round.game.next_round();
+                                round.number += 1;
+                                let announcement = format!(
+                                    "ROUND {} {} {}",
+                                    round.number,
+                                    range.start(),
+                                    range.end()
+                                );
+                                round.broadcast(&announcement);
+                                continue;
+                            }
+                        },
+                    }
+                }
+                "QUIT" => {
+                    drop(round);
+                    writeln!(writer, "BYE")?;
+                    break;
+                }
+                _ => format!("ERR unknown command '{command}'"),
+            }
+        };
+        writeln!(writer, "{reply}")?;
+    }
+    Ok(())
+}
+
+/// A minimal client for the line protocol, used by tests and scripts.
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Client> {
+        let stream = TcpStream::connect(addr)?;
+        Ok(Client {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        })
+    }
+
+    pub fn send(&mut self, line: &str) -> io::Result<()> {
+        writeln!(self.writer, "{line}")
+    }
+
+    /// The next line from the server, without its newline.
+    pub fn recv(&mut self) -> io::Result<String> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(line.trim_end().to_string())
+    }
+
+    /// Sends `line` and returns the reply.
+    pub fn request(&mut self, line: &str) -> io::Result<String> {
+        self.send(line)?;
+        self.recv()
+    }
+}
@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::adaptive::Skill;
+use crate::difficulty::Difficulty;
+use crate::game::Outcome;
+use crate::i18n::{message, plural};
+
+/// Lifetime statistics across every game played on this machine.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub total_guesses: u64,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// Best win per difficulty, keyed by the difficulty's display name.
+    pub best_games: BTreeMap<String, BestGame>,
+    /// Adaptive-mode skill per player name.
+    pub skills: BTreeMap<String, Skill>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BestGame {
+    pub attempts: u32,
+    pub score: u32,
+}
+
+impl Stats {
+    /// Adds a finished game. Games the player quit are not counted.
+    pub fn record(&mut self, difficulty: Difficulty, outcome: Outcome) {
+        if let Outcome::Quit { .. } = outcome {
+            return;
+        }
+        self.games_played += 1;
+
+        match outcome {
+            Outcome::Won { attempts, score } => {
+                self.wins += 1;
+                self.total_guesses += u64::from(attempts);
+                self.current_streak += 1;
+                self.best_streak = self.best_streak.max(self.current_streak);
+
+                let candidate = BestGame { attempts, score };
+                self.best_games
+                    .entry(difficulty.to_string())
+                    .and_modify(|best| {
+                        if attempts < best.attempts {
+                            *best = candidate;
+                        }
+                    })
+                    .or_insert(candidate);
+            }
+            Outcome::Lost { attempts, .. } => {
+                self.total_guesses += u64::from(attempts);
+                self.current_streak = 0;
+            }
+            Outcome::Quit { .. } => {}
+        }
+    }
+
+    pub fn average_guesses(&self) -> Option<f64> {
+        if self.games_played == 0 {
+            None
+        } else {
+            Some(self.total_guesses as f64 / f64::from(self.games_played))
+        }
+    }
+
+    pub fn write_report<O: Write>(&self, mut output: O) -> io::Result<()> {
+        let average = match self.average_guesses() {
+            Some(average) => format!("{average:.2}"),
+            None => "-".to_string(),
+        };
+        let rows = [
+            ("stats.games-played", self.games_played.to_string()),
+            ("stats.wins", self.wins.to_string()),
+            ("stats.average", average),
+            ("stats.current-streak", self.current_streak.to_string()),
+            ("stats.best-streak", self.best_streak.to_string()),
+        ];
+        for (key, value) in rows {
+            writeln!(output, "{:<16} {value}", message(key, &[]))?;
+        }
+
+        if !self.best_games.is_empty() {
+            writeln!(output, "{}", message("stats.best-games", &[]))?;
+            for (difficulty, best) in &self.best_games {
+                let best = plural("stats.best-game", best.attempts, &[("score", &best.score)]);
+                writeln!(output, "  {difficulty:<20} {best}")?;
+            }
+        }
+
+        if !self.skills.is_empty() {
+            writeln!(output, "{}", message("stats.ratings", &[]))?;
+            for (player, skill) in &self.skills {
+                let rating = format!("{:.0}", skill.rating);
+                let rate = format!("{:.0}", skill.win_rate().unwrap_or(0.0) * 100.0);
+                let line = plural(
+                    "stats.rating",
+                    skill.games,
+                    &[("rating", &rating), ("rate", &rate)],
+                );
+                writeln!(output, "  {player:<20} {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum StatsError {
+    Io(io::Error),
+    /// The file exists but could not be parsed. It has been moved to
+    /// `backup` so a fresh file can take its place.
+    Corrupt {
+        backup: PathBuf,
+    },
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatsError::Io(err) => write!(f, "could not access stats file: {err}"),
+            StatsError::Corrupt { backup } => write!(
+                f,
+                "stats file was corrupt and has been moved to {}",
+                backup.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StatsError {}
+
+impl From<io::Error> for StatsError {
+    fn from(err: io::Error) -> Self {
+        StatsError::Io(err)
+    }
+}
+
+/// Reads and writes [`Stats`] as JSON at a fixed path.
+pub struct StatsStore {
+    path: PathBuf,
+}
+
+impl StatsStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The store under the user's data directory.
+    pub fn default_location() -> Option<Self> {
+        data_dir().map(|dir| Self::new(dir.join("stats.json")))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the stats, treating a missing file as a fresh start.
+    pub fn load(&self) -> Result<Stats, StatsError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Stats::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        serde_json::from_str(&contents).map_err(|_| {
+            let backup = self.path.with_extension("json.corrupt");
+            match fs::rename(&self.path, &backup) {
+                Ok(()) => StatsError::Corrupt { backup },
+                Err(err) => StatsError::Io(err),
+            }
+        })
+    }
+
+    /// Writes to a temporary file first so a crash never leaves a
+    /// half-written stats file behind.
+    pub fn save(&self, stats: &Stats) -> Result<(), StatsError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(stats).map_err(io::Error::from)?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    pub fn reset(&self) -> Result<(), StatsError> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `$GUESSING_GAME_DATA_DIR`, else `$XDG_DATA_HOME/guessing_game`, else
+/// `~/.local/share/guessing_game`.
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("GUESSING_GAME_DATA_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = env::var_os("XDG_DATA_HOME") {
+        return Some(PathBuf::from(dir).join("guessing_game"));
+    }
+    env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join(".local")
+            .join("share")
+            .join("guessing_game")
+    })
+}
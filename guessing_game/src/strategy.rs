@@ -0,0 +1,329 @@
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use crate::difficulty::Difficulty;
+use crate::game::Game;
+use crate::i18n::message;
+use crate::reverse::Guesser;
+
+/// An automatic player: proposes guesses and learns from the `Ordering` the
+/// game answers with.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    /// Forgets everything and starts on a new secret drawn from `range`.
+    fn reset(&mut self, range: RangeInclusive<i64>);
+
+    fn next_guess(&mut self, rng: &mut dyn RngCore) -> i64;
+
+    /// How `guess` compared to the secret number.
+    fn feedback(&mut self, guess: i64, ordering: Ordering);
+}
+
+/// Always guesses the middle of what is left.
+#[derive(Default)]
+pub struct BinarySearch {
+    guesser: Guesser,
+}
+
+/// Guesses uniformly at random among the numbers still possible.
+#[derive(Default)]
+pub struct RandomGuess {
+    guesser: Guesser,
+}
+
+/// Counts up from the lowest number still possible.
+#[derive(Default)]
+pub struct Linear {
+    guesser: Guesser,
+}
+
+/// Bisects like a person would: aims roughly for the middle, but misses by
+/// up to `noise` of the remaining width either way.
+pub struct HumanLike {
+    guesser: Guesser,
+    noise: f64,
+}
+
+impl HumanLike {
+    pub fn new(noise: f64) -> Self {
+        Self {
+            guesser: Guesser::default(),
+            noise,
+        }
+    }
+}
+
+impl Default for HumanLike {
+    fn default() -> Self {
+        Self::new(0.25)
+    }
+}
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &str {
+        "binary"
+    }
+
+    fn reset(&mut self, range: RangeInclusive<i64>) {
+        self.guesser = Guesser::new(range);
+    }
+
+    fn next_guess(&mut self, _rng: &mut dyn RngCore) -> i64 {
+        self.guesser.next_guess()
+    }
+
+    fn feedback(&mut self, guess: i64, ordering: Ordering) {
+        let _ = self.guesser.narrow(guess, ordering);
+    }
+}
+
+impl Strategy for RandomGuess {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn reset(&mut self, range: RangeInclusive<i64>) {
+        self.guesser = Guesser::new(range);
+    }
+
+    fn next_guess(&mut self, rng: &mut dyn RngCore) -> i64 {
+        rng.gen_range(self.guesser.remaining())
+    }
+
+    fn feedback(&mut self, guess: i64, ordering: Ordering) {
+        let _ = self.guesser.narrow(guess, ordering);
+    }
+}
+
+impl Strategy for Linear {
+    fn name(&self) -> &str {
+        "linear"
+    }
+
+    fn reset(&mut self, range: RangeInclusive<i64>) {
+        self.guesser = Guesser::new(range);
+    }
+
+    fn next_guess(&mut self, _rng: &mut dyn RngCore) -> i64 {
+        *self.guesser.remaining().start()
+    }
+
+    fn feedback(&mut self, guess: i64, ordering: Ordering) {
+        let _ = self.guesser.narrow(guess, ordering);
+    }
+}
+
+impl Strategy for HumanLike {
+    fn name(&self) -> &str {
+        "human"
+    }
+
+    fn reset(&mut self, range: RangeInclusive<i64>) {
+        self.guesser = Guesser::new(range);
+    }
+
+    fn next_guess(&mut self, rng: &mut dyn RngCore) -> i64 {
+        let remaining = self.guesser.remaining();
+        let (low, high) = (*remaining.start() as f64, *remaining.end() as f64);
+        let miss = (high - low) / 2.0 * self.noise * rng.gen_range(-1.0..=1.0);
+        let aim = low + (high - low) / 2.0 + miss;
+        (aim.round() as i64).clamp(*remaining.start(), *remaining.end())
+    }
+
+    fn feedback(&mut self, guess: i64, ordering: Ordering) {
+        let _ = self.guesser.narrow(guess, ordering);
+    }
+}
+
+/// Every built-in strategy, in the order `bench` reports them.
+pub fn all_strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(BinarySearch::default()),
+        Box::new(HumanLike::default()),
+        Box::new(RandomGuess::default()),
+        Box::new(Linear::default()),
+    ]
+}
+
+/// Guesses [`solve`] allows before giving up on a round. Enough for
+/// `linear` on the hard range, but it stops `linear` and `random` from
+/// running for hours on huge custom ranges.
+pub const MAX_GUESSES: u32 = 10_000;
+
+/// Lets `strategy` play the current round of `game` to the end and returns
+/// how many guesses it needed, or `None` if it hit [`MAX_GUESSES`] first.
+pub fn solve<R: Rng>(
+    strategy: &mut dyn Strategy,
+    game: &mut Game<R>,
+    rng: &mut dyn RngCore,
+) -> Option<u32> {
+    strategy.reset(game.range());
+    while game.attempts() < MAX_GUESSES {
+        let guess = strategy.next_guess(rng);
+        let ordering = game.guess(guess);
+        if ordering == Ordering::Equal {
+            return Some(game.attempts());
+        }
+        strategy.feedback(guess, ordering);
+    }
+    None
+}
+
+/// Guess counts for one strategy over a benchmark run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub name: String,
+    /// Guesses per solved round, sorted ascending.
+    pub guesses: Vec<u32>,
+    /// Rounds given up on after [`MAX_GUESSES`].
+    pub failed: u32,
+}
+
+impl BenchResult {
+    /// NaN when no round was solved, as for the median.
+    pub fn mean(&self) -> f64 {
+        let total: u64 = self.guesses.iter().map(|&g| u64::from(g)).sum();
+        total as f64 / self.guesses.len() as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        let n = self.guesses.len();
+        if n == 0 {
+            f64::NAN
+        } else if n % 2 == 1 {
+            f64::from(self.guesses[n / 2])
+        } else {
+            (f64::from(self.guesses[n / 2 - 1]) + f64::from(self.guesses[n / 2])) / 2.0
+        }
+    }
+
+    pub fn max(&self) -> u32 {
+        self.guesses.last().copied().unwrap_or(0)
+    }
+
+    /// Counts per bucket of guess counts, as `(first, last, count)`. Uses one
+    /// bucket per count when they fit in `buckets`, equal-width ones otherwise.
+    pub fn histogram(&self, buckets: u32) -> Vec<(u32, u32, usize)> {
+        let (Some(&min), Some(&max)) = (self.guesses.first(), self.guesses.last()) else {
+            return Vec::new();
+        };
+        let width = (max - min) / buckets.max(1) + 1;
+
+        let mut histogram = Vec::new();
+        let mut start = min;
+        while start <= max {
+            let end = start + width - 1;
+            let count = self
+                .guesses
+                .iter()
+                .filter(|&&g| (start..=end).contains(&g))
+                .count();
+            histogram.push((start, end, count));
+            start += width;
+        }
+        histogram
+    }
+}
+
+/// Plays `games` rounds with every strategy. Each strategy faces the same
+/// secret numbers, drawn from a generator seeded with `seed`.
+pub fn bench(
+    strategies: &mut [Box<dyn Strategy>],
+    difficulty: Difficulty,
+    games: u32,
+    seed: u64,
+) -> Vec<BenchResult> {
+    strategies
+        .iter_mut()
+        .map(|strategy| {
+            let mut game = Game::with_difficulty(StdRng::seed_from_u64(seed), difficulty);
+            // A different stream from the game's, or `random` would replay
+            // the secret-number draws and always guess right first time.
+            let mut rng = StdRng::seed_from_u64(!seed);
+            let mut guesses = Vec::new();
+            let mut failed = 0;
+            for _ in 0..games {
+                match solve(strategy.as_mut(), &mut game, &mut rng) {
+                    Some(used) => guesses.push(used),
+                    None => failed += 1,
+                }
+                game.next_round();
+            }
+            guesses.sort_unstable();
+
+            BenchResult {
+                name: strategy.name().to_string(),
+                guesses,
+                failed,
+            }
+        })
+        .collect()
+}
+
+pub fn write_bench_report<O: Write>(results: &[BenchResult], mut output: O) -> io::Result<()> {
+    writeln!(
+        output,
+        "{:<8} {:>8} {:>8} {:>6} {:>6}",
+        message("bench.strategy", &[]),
+        message("bench.mean", &[]),
+        message("bench.median", &[]),
+        message("bench.max", &[]),
+        message("bench.failed", &[])
+    )?;
+    for result in results {
+        if result.guesses.is_empty() {
+            writeln!(
+                output,
+                "{:<8} {:>8} {:>8} {:>6} {:>6}",
+                result.name, "-", "-", "-", result.failed
+            )?;
+            continue;
+        }
+        writeln!(
+            output,
+            "{:<8} {:>8.2} {:>8.1} {:>6} {:>6}",
+            result.name,
+            result.mean(),
+            result.median(),
+            result.max(),
+            result.failed
+        )?;
+    }
+    if results.iter().any(|result| result.failed > 0) {
+        writeln!(output)?;
+        writeln!(
+            output,
+            "{}",
+            message("bench.failed-note", &[("max", &MAX_GUESSES)])
+        )?;
+    }
+
+    for result in results {
+        writeln!(output)?;
+        writeln!(output, "{}:", result.name)?;
+        if result.guesses.is_empty() {
+            writeln!(output, "{:>11} | {}", "", message("bench.unsolved", &[]))?;
+            continue;
+        }
+        let histogram = result.histogram(20);
+        let tallest = histogram
+            .iter()
+            .map(|&(_, _, count)| count)
+            .max()
+            .unwrap_or(1);
+        for (start, end, count) in histogram {
+            let label = if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            };
+            let bar = "#".repeat((count * 40).div_ceil(tallest.max(1)));
+            writeln!(output, "{label:>11} | {bar} {count}")?;
+        }
+    }
+    Ok(())
+}
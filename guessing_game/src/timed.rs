@@ -0,0 +1,114 @@
+use rand::Rng;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::game::{Game, Outcome};
+use crate::i18n::{message, plural};
+use crate::input::LineSource;
+use crate::play::{direction, play_loop};
+
+/// Optional clocks on a game: one for the whole game, one for each guess.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TimeLimits {
+    pub per_game: Option<Duration>,
+    pub per_guess: Option<Duration>,
+}
+
+impl TimeLimits {
+    pub fn is_set(&self) -> bool {
+        self.per_game.is_some() || self.per_guess.is_some()
+    }
+
+    /// How long to wait for the next line when `elapsed` of the game has
+    /// gone and the player has been on the current guess for `thinking`,
+    /// or `None` to wait forever.
+    pub fn timeout(&self, elapsed: Duration, thinking: Duration) -> Option<Duration> {
+        let game_left = self.per_game.map(|limit| limit.saturating_sub(elapsed));
+        let guess_left = self.per_guess.map(|limit| limit.saturating_sub(thinking));
+        match (game_left, guess_left) {
+            (Some(game_left), Some(guess_left)) => Some(game_left.min(guess_left)),
+            (game_left, guess_left) => game_left.or(guess_left),
+        }
+    }
+}
+
+/// Plays one game against the clock. Use a source such as
+/// [`crate::input::ChannelLines`] so a timeout can interrupt waiting for
+/// input; a plain `BufRead` only notices the clock between lines.
+pub fn play_timed<R: Rng, I: LineSource, O: Write>(
+    game: &mut Game<R>,
+    limits: TimeLimits,
+    mut input: I,
+    output: O,
+) -> io::Result<Outcome> {
+    play_loop(game, &mut input, output, direction, &limits, true)
+}
+
+/// Totals for a speedrun.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Speedrun {
+    pub outcomes: Vec<Outcome>,
+    pub round_times: Vec<Duration>,
+    pub total: Duration,
+}
+
+impl Speedrun {
+    pub fn wins(&self) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome, Outcome::Won { .. }))
+            .count()
+    }
+}
+
+/// Plays `rounds` games back to back with the next secret each time and
+/// reports the total time. Quitting stops the run early.
+pub fn speedrun<R: Rng, I: LineSource, O: Write>(
+    game: &mut Game<R>,
+    rounds: u32,
+    limits: TimeLimits,
+    mut input: I,
+    mut output: O,
+) -> io::Result<Speedrun> {
+    let started = Instant::now();
+    let mut outcomes = Vec::new();
+    let mut round_times = Vec::new();
+
+    for round in 1..=rounds {
+        if round > 1 {
+            game.next_round();
+        }
+        writeln!(
+            output,
+            "{}",
+            message("speedrun.round", &[("round", &round), ("rounds", &rounds)])
+        )?;
+
+        let round_started = Instant::now();
+        let outcome = play_loop(game, &mut input, &mut output, direction, &limits, true)?;
+        round_times.push(round_started.elapsed());
+        outcomes.push(outcome);
+
+        if let Outcome::Quit { .. } = outcome {
+            break;
+        }
+    }
+
+    let run = Speedrun {
+        outcomes,
+        round_times,
+        total: started.elapsed(),
+    };
+    let seconds = format!("{:.1}", run.total.as_secs_f64());
+    writeln!(
+        output,
+        "{}",
+        plural(
+            "speedrun.over",
+            // Rounds actually played: quitting ends the run early.
+            run.outcomes.len() as u32,
+            &[("wins", &run.wins()), ("seconds", &seconds)]
+        )
+    )?;
+    Ok(run)
+}
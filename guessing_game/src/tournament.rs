@@ -0,0 +1,345 @@
+use crossterm::{cursor, queue, terminal};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::io::{self, Write};
+
+use crate::difficulty::Difficulty;
+use crate::game::{Game, Outcome};
+use crate::i18n::{message, plural};
+use crate::input::LineSource;
+use crate::play::{direction, play_loop};
+use crate::timed::TimeLimits;
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+/// Blank lines printed between shared-secret turns when the screen can't be
+/// cleared, enough to push the last player's guesses out of sight.
+pub const HANDOVER_LINES: usize = 50;
+
+/// A hot-seat tournament: players take turns at the same terminal, one game
+/// each per round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    players: Vec<String>,
+    difficulty: Difficulty,
+    rounds: u32,
+    shared_secret: bool,
+    max_attempts: Option<u32>,
+    clear_screen: bool,
+}
+
+impl Tournament {
+    /// Needs 2 to 8 players with distinct, non-blank names. Plays three
+    /// rounds, each player with their own secret, until told otherwise.
+    pub fn new(players: Vec<String>, difficulty: Difficulty) -> Result<Self, String> {
+        let players: Vec<String> = players.iter().map(|name| name.trim().to_string()).collect();
+        check_players(&players)?;
+        Ok(Self {
+            players,
+            difficulty,
+            rounds: 3,
+            shared_secret: false,
+            max_attempts: None,
+            clear_screen: false,
+        })
+    }
+
+    pub fn with_rounds(mut self, rounds: u32) -> Self {
+        self.rounds = rounds.max(1);
+        self
+    }
+
+    /// Everyone guesses the same secret each round instead of their own.
+    pub fn with_shared_secret(mut self, shared: bool) -> Self {
+        self.shared_secret = shared;
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Clears the screen with terminal escapes between shared-secret turns
+    /// instead of printing [`HANDOVER_LINES`] blank lines. Only for output
+    /// that is a terminal.
+    pub fn with_clear_screen(mut self, clear: bool) -> Self {
+        self.clear_screen = clear;
+        self
+    }
+
+    pub fn players(&self) -> &[String] {
+        &self.players
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+}
+
+/// Checks there are 2 to 8 players with distinct, non-blank names.
+pub fn check_players(players: &[String]) -> Result<(), String> {
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players.len()) {
+        return Err(format!(
+            "a tournament needs {MIN_PLAYERS} to {MAX_PLAYERS} players, not {}",
+            players.len()
+        ));
+    }
+    if players.iter().any(|name| name.trim().is_empty()) {
+        return Err("player names cannot be blank".to_string());
+    }
+    for (i, name) in players.iter().enumerate() {
+        if players[..i]
+            .iter()
+            .any(|other| other.trim().eq_ignore_ascii_case(name.trim()))
+        {
+            return Err(format!("player '{}' is listed twice", name.trim()));
+        }
+    }
+    Ok(())
+}
+
+/// One player's totals so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerScore {
+    pub name: String,
+    /// Sum of the scores of every round won.
+    pub points: u32,
+    pub wins: u32,
+    /// Guesses across every round, won or not.
+    pub attempts: u32,
+}
+
+impl PlayerScore {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            points: 0,
+            wins: 0,
+            attempts: 0,
+        }
+    }
+
+    fn record(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Won { attempts, score } => {
+                self.points += score;
+                self.wins += 1;
+                self.attempts += attempts;
+            }
+            Outcome::Lost { attempts, .. } | Outcome::Quit { attempts } => {
+                self.attempts += attempts
+            }
+        }
+    }
+
+    /// Better first: more points, then more wins, then fewer attempts.
+    fn compare(&self, other: &Self) -> Ordering {
+        other
+            .points
+            .cmp(&self.points)
+            .then(other.wins.cmp(&self.wins))
+            .then(self.attempts.cmp(&other.attempts))
+    }
+}
+
+/// A place in the final table. Players level on every tie-breaker share a
+/// rank.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub rank: usize,
+    pub score: PlayerScore,
+}
+
+/// Orders scores into standings; ties keep the order players were listed.
+pub fn standings(scores: &[PlayerScore]) -> Vec<Standing> {
+    let mut sorted = scores.to_vec();
+    sorted.sort_by(PlayerScore::compare);
+
+    let mut standings: Vec<Standing> = Vec::with_capacity(sorted.len());
+    for (i, score) in sorted.into_iter().enumerate() {
+        let rank = match standings.last() {
+            Some(previous) if previous.score.compare(&score) == Ordering::Equal => previous.rank,
+            _ => i + 1,
+        };
+        standings.push(Standing { rank, score });
+    }
+    standings
+}
+
+/// How a tournament went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TournamentResult {
+    pub standings: Vec<Standing>,
+    pub rounds_played: u32,
+    /// Someone quit or input ran out before the last round finished.
+    pub abandoned: bool,
+}
+
+/// Plays every round, printing the standings at the end. Each game's secret
+/// comes from a seed drawn from `rng`, so a seeded `rng` replays the whole
+/// tournament. With a shared secret, earlier turns are cleared away before
+/// each player's turn so nobody can read how the others narrowed it down.
+pub fn play_tournament<R: Rng, I: LineSource, O: Write>(
+    rng: &mut R,
+    tournament: &Tournament,
+    mut input: I,
+    mut output: O,
+) -> io::Result<TournamentResult> {
+    let mut scores: Vec<PlayerScore> = tournament
+        .players
+        .iter()
+        .map(|name| PlayerScore::new(name))
+        .collect();
+    let mut rounds_played = 0;
+    let mut abandoned = false;
+
+    'rounds: for round in 1..=tournament.rounds {
+        write_round(tournament, round, &mut output)?;
+        let shared_seed: u64 = rng.gen();
+
+        for (turn, score) in scores.iter_mut().enumerate() {
+            if tournament.shared_secret && turn > 0 {
+                hand_over(tournament.clear_screen, &mut output)?;
+                write_round(tournament, round, &mut output)?;
+            }
+            let seed = if tournament.shared_secret {
+                shared_seed
+            } else {
+                rng.gen()
+            };
+            let mut game =
+                Game::with_difficulty(StdRng::seed_from_u64(seed), tournament.difficulty)
+                    .with_max_attempts(tournament.max_attempts);
+
+            writeln!(output)?;
+            writeln!(
+                output,
+                "{}",
+                message("tournament.turn", &[("name", &score.name)])
+            )?;
+            let outcome = play_loop(
+                &mut game,
+                &mut input,
+                &mut output,
+                direction,
+                &TimeLimits::default(),
+                true,
+            )?;
+            score.record(outcome);
+
+            if let Outcome::Quit { .. } = outcome {
+                abandoned = true;
+                break 'rounds;
+            }
+        }
+        rounds_played = round;
+        writeln!(output)?;
+    }
+
+    let standings = standings(&scores);
+    if abandoned {
+        writeln!(output, "{}", message("tournament.abandoned", &[]))?;
+    }
+    write_standings(&standings, rounds_played, &mut output)?;
+    Ok(TournamentResult {
+        standings,
+        rounds_played,
+        abandoned,
+    })
+}
+
+fn write_round<W: Write>(tournament: &Tournament, round: u32, mut output: W) -> io::Result<()> {
+    writeln!(
+        output,
+        "{}",
+        message(
+            "tournament.round",
+            &[("round", &round), ("rounds", &tournament.rounds)]
+        )
+    )?;
+    if tournament.shared_secret {
+        writeln!(output, "{}", message("tournament.shared", &[]))?;
+    }
+    Ok(())
+}
+
+/// Gets the last player's turn off the screen before the next one starts.
+fn hand_over<W: Write>(clear_screen: bool, mut output: W) -> io::Result<()> {
+    if clear_screen {
+        queue!(
+            output,
+            terminal::Clear(terminal::ClearType::All),
+            terminal::Clear(terminal::ClearType::Purge),
+            cursor::MoveTo(0, 0)
+        )
+    } else {
+        write!(output, "{}", "\n".repeat(HANDOVER_LINES))
+    }
+}
+
+/// Prints the standings table and who won.
+pub fn write_standings<W: Write>(
+    standings: &[Standing],
+    rounds_played: u32,
+    mut output: W,
+) -> io::Result<()> {
+    writeln!(
+        output,
+        "{}",
+        plural("tournament.standings", rounds_played, &[])
+    )?;
+
+    let width = standings
+        .iter()
+        .map(|standing| standing.score.name.chars().count())
+        .chain([message("tournament.player", &[]).chars().count()])
+        .max()
+        .unwrap_or(0);
+    writeln!(
+        output,
+        "      {:<width$}  {:>6}  {:>4}  {:>8}",
+        message("tournament.player", &[]),
+        message("tournament.points", &[]),
+        message("tournament.wins", &[]),
+        message("tournament.attempts", &[]),
+    )?;
+    for (i, standing) in standings.iter().enumerate() {
+        let shared = standings
+            .iter()
+            .enumerate()
+            .any(|(j, other)| j != i && other.rank == standing.rank);
+        let rank = if shared {
+            format!("={}", standing.rank)
+        } else {
+            standing.rank.to_string()
+        };
+        let score = &standing.score;
+        writeln!(
+            output,
+            "  {rank:>3}. {:<width$}  {:>6}  {:>4}  {:>8}",
+            score.name, score.points, score.wins, score.attempts
+        )?;
+    }
+
+    let leaders: Vec<&str> = standings
+        .iter()
+        .filter(|standing| standing.rank == 1)
+        .map(|standing| standing.score.name.as_str())
+        .collect();
+    match leaders.as_slice() {
+        [] => {}
+        [winner] => writeln!(
+            output,
+            "{}",
+            message("tournament.winner", &[("name", winner)])
+        )?,
+        tied => writeln!(
+            output,
+            "{}",
+            message("tournament.tie", &[("names", &tied.join(", "))])
+        )?,
+    }
+    Ok(())
+}
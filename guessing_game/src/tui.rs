@@ -0,0 +1,317 @@
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{PrintStyledContent, Stylize};
+use crossterm::{cursor, execute, queue, terminal};
+use rand::Rng;
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use crate::game::HINT_PENALTY;
+use crate::game::{Game, Outcome};
+use crate::i18n::message;
+use crate::input::{parse_command, Command};
+use crate::play::direction;
+
+/// Everything one frame of the full-screen UI shows.
+pub struct View<'a> {
+    pub range: RangeInclusive<i64>,
+    /// The numbers neither hints nor guesses have ruled out yet.
+    pub possible: RangeInclusive<i64>,
+    pub history: &'a [(i64, Ordering)],
+    pub attempts: u32,
+    pub attempts_left: Option<u32>,
+    pub elapsed: Duration,
+    /// What the player has typed so far.
+    pub input: &'a str,
+    /// Feedback on the last key press, e.g. an input error.
+    pub message: &'a str,
+}
+
+/// A `width`-cell bar over the whole range where `#` marks cells that still
+/// hold possible numbers and `.` marks ruled-out ones.
+pub fn interval_bar(
+    range: &RangeInclusive<i64>,
+    possible: &RangeInclusive<i64>,
+    width: usize,
+) -> String {
+    let start = *range.start() as i128;
+    let size = *range.end() as i128 - start + 1;
+    let width = width.max(1) as i128;
+
+    (0..width)
+        .map(|cell| {
+            let first = start + cell * size / width;
+            let last = (start + (cell + 1) * size / width - 1).max(first);
+            let overlaps = first <= *possible.end() as i128 && last >= *possible.start() as i128;
+            if overlaps {
+                '#'
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+/// Lines every frame has besides the guesses.
+const FRAME_LINES: usize = 12;
+
+/// Lays out one frame as plain lines, `width` columns wide and at most
+/// `height` tall. When the guesses don't all fit, the latest ones are shown
+/// after a `...` line; on a very short terminal the top lines go first, so
+/// the input line always shows.
+pub fn render(view: &View, width: usize, height: usize) -> Vec<String> {
+    let possible = &view.possible;
+    let bar_width = width.saturating_sub(4).max(10);
+    let seconds = view.elapsed.as_secs();
+
+    let mut lines = vec![
+        message("game.intro", &[]),
+        String::new(),
+        message(
+            "tui.possible",
+            &[
+                ("min", possible.start()),
+                ("max", possible.end()),
+                ("low", view.range.start()),
+                ("high", view.range.end()),
+            ],
+        ),
+        format!("[{}]", interval_bar(&view.range, possible, bar_width)),
+        String::new(),
+    ];
+
+    let attempts = match view.attempts_left {
+        Some(left) => message("tui.attempts-left", &[("count", &left)]),
+        None => message("tui.attempts", &[("count", &view.attempts)]),
+    };
+    let time = format!("{:02}:{:02}", seconds / 60, seconds % 60);
+    lines.push(format!(
+        "{attempts}   {}",
+        message("tui.time", &[("time", &time)])
+    ));
+    lines.push(String::new());
+
+    lines.push(message("tui.guesses", &[]));
+    let room = height.saturating_sub(FRAME_LINES);
+    let mut skip = 0;
+    if view.history.len() > room {
+        skip = view.history.len() - room.saturating_sub(1);
+        if room > 0 {
+            lines.push("     ...".to_string());
+        }
+    }
+    for (number, &(guess, ordering)) in view.history.iter().enumerate().skip(skip) {
+        lines.push(format!(
+            "{:>4}. {guess:>8}  {}",
+            number + 1,
+            direction(guess, ordering)
+        ));
+    }
+    lines.push(String::new());
+
+    lines.push(format!("> {}_", view.input));
+    lines.push(view.message.to_string());
+    lines.push(message("tui.keys", &[]));
+    if lines.len() > height {
+        lines.drain(..lines.len() - height);
+    }
+    lines
+}
+
+/// Plays one line typed into the full-screen UI, a guess or one of the
+/// words [`crate::play::play`] understands, and returns the status line to
+/// show with the outcome if it ended the game.
+pub fn submit<R: Rng>(game: &mut Game<R>, line: &str) -> (String, Option<Outcome>) {
+    let command = match parse_command(line, &game.range()) {
+        Ok(command) => command,
+        Err(err) => return (err.to_string(), None),
+    };
+    let status = match command {
+        Command::Guess(guess) => {
+            let turn = game.take_turn(guess);
+            let status = match turn.outcome {
+                Some(Outcome::Lost { secret_number, .. }) => {
+                    message("game.out-of-attempts", &[("secret", &secret_number)])
+                }
+                _ => direction(guess, turn.ordering),
+            };
+            return (status, turn.outcome);
+        }
+        Command::Hint => match game.hint(game.possible_range()) {
+            Some(hinted) => message(
+                "game.hint",
+                &[
+                    ("min", hinted.start()),
+                    ("max", hinted.end()),
+                    ("penalty", &HINT_PENALTY),
+                ],
+            ),
+            None => message("game.no-hint", &[]),
+        },
+        Command::Range => {
+            let possible = game.possible_range();
+            message(
+                "game.range-now",
+                &[("min", possible.start()), ("max", possible.end())],
+            )
+        }
+        // The frame already lists every guess.
+        Command::History if game.history().is_empty() => message("game.no-history", &[]),
+        Command::History => String::new(),
+        Command::Undo => match game.undo() {
+            Some(guess) => message("game.undone", &[("guess", &guess)]),
+            None => message("game.no-undo", &[]),
+        },
+        Command::GiveUp => {
+            let secret = game.secret_number();
+            let outcome = Outcome::Lost {
+                attempts: game.attempts(),
+                secret_number: secret,
+            };
+            return (
+                message("game.gave-up", &[("secret", &secret)]),
+                Some(outcome),
+            );
+        }
+        Command::Quit => {
+            let outcome = Outcome::Quit {
+                attempts: game.attempts(),
+            };
+            return (message("game.quit", &[]), Some(outcome));
+        }
+    };
+    (status, None)
+}
+
+/// Puts the terminal back the way it was, even if the game panics.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn draw(view: &View, colors: bool) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let mut stdout = io::stdout().lock();
+    queue!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(terminal::ClearType::All)
+    )?;
+    for (row, line) in render(view, usize::from(width), usize::from(height))
+        .iter()
+        .enumerate()
+    {
+        queue!(stdout, cursor::MoveTo(0, row as u16))?;
+        // The interval bar is the only line drawn in color.
+        if colors && line.starts_with('[') {
+            for cell in line.chars() {
+                match cell {
+                    '#' => queue!(stdout, PrintStyledContent(cell.green()))?,
+                    '.' => queue!(stdout, PrintStyledContent(cell.dark_grey()))?,
+                    _ => write!(stdout, "{cell}")?,
+                }
+            }
+        } else {
+            write!(stdout, "{line}")?;
+        }
+    }
+    stdout.flush()
+}
+
+/// Plays `game` full-screen, with a colored interval bar if `colors`. Only
+/// call this when stdin and stdout are a terminal; otherwise use
+/// [`crate::play::play`].
+pub fn play_tui<R: Rng>(game: &mut Game<R>, colors: bool) -> io::Result<Outcome> {
+    let _guard = TerminalGuard::enter()?;
+    let started = Instant::now();
+    let mut input = String::new();
+    let mut status = String::new();
+    let mut outcome = None;
+    let mut elapsed = Duration::ZERO;
+
+    loop {
+        // The clock stops once the game is decided.
+        if outcome.is_none() {
+            elapsed = started.elapsed();
+        }
+        draw(
+            &View {
+                range: game.range(),
+                possible: game.possible_range(),
+                history: game.history(),
+                attempts: game.attempts(),
+                attempts_left: game.attempts_left(),
+                elapsed,
+                input: &input,
+                message: &status,
+            },
+            colors,
+        )?;
+
+        // Redraw at least every quarter second so the timer keeps moving.
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+
+        if let Some(outcome) = outcome {
+            // Any key leaves the final screen.
+            return Ok(outcome);
+        }
+
+        match code {
+            KeyCode::Esc => {
+                return Ok(Outcome::Quit {
+                    attempts: game.attempts(),
+                })
+            }
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(Outcome::Quit {
+                    attempts: game.attempts(),
+                })
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                status.clear();
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                let (reply, ended) = submit(game, &input);
+                if let Some(quit @ Outcome::Quit { .. }) = ended {
+                    return Ok(quit);
+                }
+                status = reply;
+                outcome = ended;
+                if outcome.is_some() {
+                    status.push_str("  ");
+                    status.push_str(&message("tui.press-any-key", &[]));
+                }
+                input.clear();
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use guessing_game::adaptive::{
+    play_adaptive_round, Challenge, Skill, BASELINE_RATING, INITIAL_RATING, K_FACTOR, SLACK_POINTS,
+};
+use guessing_game::cli::{Args, Command};
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::Outcome;
+use guessing_game::stats::Stats;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn skill(rating: f64) -> Skill {
+    Skill {
+        rating,
+        ..Skill::default()
+    }
+}
+
+fn challenge(max: i64, max_attempts: u32) -> Challenge {
+    Challenge {
+        difficulty: Difficulty::Custom { min: 1, max },
+        max_attempts,
+    }
+}
+
+const WON: Outcome = Outcome::Won {
+    attempts: 5,
+    score: 100,
+};
+const LOST: Outcome = Outcome::Lost {
+    attempts: 5,
+    secret_number: 1,
+};
+
+#[test]
+fn challenges_are_rated_by_attempts_to_spare() {
+    // 1..=100 takes bisection 7 guesses.
+    assert_eq!(challenge(100, 7).rating(), BASELINE_RATING);
+    assert_eq!(
+        challenge(100, 9).rating(),
+        BASELINE_RATING - 2.0 * SLACK_POINTS
+    );
+    assert_eq!(challenge(100, 6).rating(), BASELINE_RATING + SLACK_POINTS);
+}
+
+#[test]
+fn a_new_player_gets_a_gentle_round() {
+    let new = Skill::default();
+    assert_eq!(new.rating, INITIAL_RATING);
+    assert_eq!(new.win_rate(), None);
+
+    let next = new.next_challenge(0.7);
+    assert_eq!(next.difficulty, Difficulty::Custom { min: 1, max: 100 });
+    assert!(next.max_attempts > next.difficulty.optimal_guesses());
+}
+
+#[test]
+fn next_challenge_aims_at_the_target_win_rate() {
+    for rating in [600.0, 1000.0, 1200.0, 1500.0, 1900.0, 2400.0] {
+        for target in [0.5, 0.7, 0.9] {
+            let player = skill(rating);
+            let expected = player.expected(&player.next_challenge(target));
+            // Attempts are whole, so the odds can only land near the target.
+            assert!(
+                (expected - target).abs() < 0.15,
+                "rating {rating}, target {target}: expected {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn better_players_get_bigger_ranges() {
+    let sizes: Vec<u128> = [900.0, 1200.0, 1500.0, 2000.0]
+        .iter()
+        .map(|&rating| skill(rating).next_challenge(0.7).difficulty.size())
+        .collect();
+
+    assert!(sizes.windows(2).all(|pair| pair[0] < pair[1]), "{sizes:?}");
+}
+
+#[test]
+fn wins_raise_and_losses_lower_the_rating() {
+    let round = challenge(100, 10);
+    let mut player = Skill::default();
+
+    let gain = player.record(&round, WON);
+    assert!(gain > 0.0 && gain < K_FACTOR);
+    let loss = player.record(&round, LOST);
+    assert!(loss < 0.0 && loss > -K_FACTOR);
+    assert_eq!(player.record(&round, Outcome::Quit { attempts: 2 }), 0.0);
+
+    assert_eq!(player.games, 2);
+    assert_eq!(player.wins, 1);
+    assert_eq!(player.win_rate(), Some(0.5));
+}
+
+#[test]
+fn upsets_move_the_rating_more() {
+    let easy = challenge(10, 10);
+    let hard = challenge(10_000, 10);
+
+    let mut player = Skill::default();
+    let expected_win = player.record(&easy, WON);
+    let mut player = Skill::default();
+    let surprise_win = player.record(&hard, WON);
+
+    assert!(surprise_win > expected_win);
+}
+
+#[test]
+fn giving_up_costs_rating_and_quitting_does_not() {
+    let mut player = Skill::default();
+    let mut output = Vec::new();
+    let (_, outcome) = play_adaptive_round(
+        &mut StdRng::seed_from_u64(3),
+        &mut player,
+        0.7,
+        &mut "quit\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    assert_eq!(outcome, Outcome::Quit { attempts: 0 });
+    assert_eq!(player, Skill::default());
+
+    let mut output = Vec::new();
+    let (round, outcome) = play_adaptive_round(
+        &mut StdRng::seed_from_u64(3),
+        &mut player,
+        0.7,
+        &mut "giveup\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    assert!(matches!(outcome, Outcome::Lost { .. }));
+    assert!(player.rating < INITIAL_RATING);
+
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.starts_with("Your rating is 1200; this round is set for a "));
+    assert!(transcript.contains(&format!(
+        "You have {} attempts to find it.",
+        round.max_attempts
+    )));
+    assert!(transcript.contains(&format!("New rating: {} (-", player.rating.round())));
+}
+
+#[test]
+fn skills_are_stored_with_the_stats() {
+    let mut stats = Stats::default();
+    stats.skills.insert("ann".to_string(), skill(1337.0));
+
+    let json = serde_json::to_string(&stats).unwrap();
+    let loaded: Stats = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, stats);
+
+    // Files from before ratings existed still load.
+    let old: Stats = serde_json::from_str(r#"{"games_played": 3, "wins": 2}"#).unwrap();
+    assert!(old.skills.is_empty());
+
+    let mut report = Vec::new();
+    loaded.write_report(&mut report).unwrap();
+    assert!(String::from_utf8(report)
+        .unwrap()
+        .contains("Ratings:\n  ann                  1337 after 0 games (0% won)\n"));
+}
+
+#[test]
+fn adaptive_flags_are_parsed() {
+    let parse = |line: &str| Args::parse(line.split_whitespace().map(String::from));
+
+    let args = parse("adaptive --player ann --target 0.6 --rounds 3").unwrap();
+    assert_eq!(args.command, Command::Adaptive);
+    assert_eq!(args.player.as_deref(), Some("ann"));
+    assert_eq!(args.target, Some(0.6));
+    assert_eq!(args.rounds, Some(3));
+
+    assert!(parse("adaptive --target 1").is_err());
+    assert!(parse("adaptive --target 0").is_err());
+}
@@ -0,0 +1,206 @@
+use guessing_game::api::{Api, HttpServer};
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::Game;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn api() -> Api {
+    Api::new(Duration::from_secs(60))
+}
+
+fn secret_for(seed: u64, difficulty: Difficulty) -> i64 {
+    Game::with_difficulty(StdRng::seed_from_u64(seed), difficulty).secret_number()
+}
+
+fn guess(api: &mut Api, id: u64, guess: i64) -> (u16, Value) {
+    let response = api.handle(
+        "POST",
+        &format!("/games/{id}/guesses"),
+        &json!({ "guess": guess }).to_string(),
+    );
+    (response.status, response.body)
+}
+
+#[test]
+fn create_guess_and_win() {
+    let mut api = api();
+    let secret = secret_for(5, Difficulty::Normal);
+
+    let created = api.handle("POST", "/games", r#"{"seed": 5}"#);
+    assert_eq!(created.status, 201);
+    assert_eq!(created.body["id"], 1);
+    assert_eq!(created.body["min"], 1);
+    assert_eq!(created.body["max"], 100);
+    assert_eq!(created.body["status"], "playing");
+
+    let (status, body) = guess(&mut api, 1, secret - 1);
+    assert_eq!(status, 200);
+    assert_eq!(body["result"], "less");
+
+    let (_, body) = guess(&mut api, 1, secret + 1);
+    assert_eq!(body["result"], "greater");
+
+    let (_, body) = guess(&mut api, 1, secret);
+    assert_eq!(body["result"], "equal");
+    assert_eq!(body["status"], "won");
+    assert_eq!(body["attempts"], 3);
+    assert_eq!(body["secret"], secret);
+
+    let (status, _) = guess(&mut api, 1, secret);
+    assert_eq!(status, 409);
+}
+
+#[test]
+fn history_lists_every_guess() {
+    let mut api = api();
+    let secret = secret_for(9, Difficulty::Hard);
+    api.handle("POST", "/games", r#"{"difficulty": "hard", "seed": 9}"#);
+    guess(&mut api, 1, 1);
+    guess(&mut api, 1, 10_000);
+
+    let shown = api.handle("GET", "/games/1", "");
+
+    assert_eq!(shown.status, 200);
+    assert_eq!(shown.body["difficulty"], "hard");
+    let expected_first = if secret == 1 { "equal" } else { "less" };
+    assert_eq!(
+        shown.body["history"][0],
+        json!({ "guess": 1, "result": expected_first })
+    );
+    assert_eq!(shown.body["history"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn running_out_of_attempts_loses() {
+    let mut api = api();
+    let secret = secret_for(4, Difficulty::custom(-5, 5).unwrap());
+    api.handle(
+        "POST",
+        "/games",
+        r#"{"min": -5, "max": 5, "seed": 4, "attempts": 1}"#,
+    );
+
+    let wrong = if secret == 5 { -5 } else { 5 };
+    let (_, body) = guess(&mut api, 1, wrong);
+
+    assert_eq!(body["status"], "lost");
+    assert_eq!(body["attempts_left"], 0);
+    assert_eq!(body["secret"], secret);
+}
+
+#[test]
+fn bad_requests_are_rejected() {
+    let mut api = api();
+    assert_eq!(api.handle("POST", "/games", "{").status, 400);
+    assert_eq!(
+        api.handle("POST", "/games", r#"{"difficulty": "insane"}"#)
+            .status,
+        400
+    );
+    assert_eq!(api.handle("POST", "/games", r#"{"min": 3}"#).status, 400);
+    assert_eq!(
+        api.handle("POST", "/games", r#"{"attempts": 0}"#).status,
+        400
+    );
+    assert_eq!(api.handle("GET", "/games/7", "").status, 404);
+    assert_eq!(api.handle("GET", "/games/abc", "").status, 404);
+    assert_eq!(api.handle("DELETE", "/games", "").status, 405);
+    assert_eq!(api.handle("GET", "/", "").status, 404);
+
+    api.handle("POST", "/games", r#"{"difficulty": "easy"}"#);
+    let (status, body) = guess(&mut api, 1, 11);
+    assert_eq!(status, 422);
+    assert_eq!(body["error"], "11 is out of range! Guess between 1 and 10.");
+    assert_eq!(
+        api.handle("POST", "/games/1/guesses", r#"{"guess": "x"}"#)
+            .status,
+        400
+    );
+}
+
+#[test]
+fn idle_sessions_expire() {
+    let mut api = Api::new(Duration::from_millis(20));
+    api.handle("POST", "/games", "");
+    assert_eq!(api.session_count(), 1);
+
+    thread::sleep(Duration::from_millis(40));
+
+    assert_eq!(api.session_count(), 0);
+    assert_eq!(api.handle("GET", "/games/1", "").status, 404);
+}
+
+#[test]
+fn serves_over_http() {
+    let server = HttpServer::bind("127.0.0.1:0", api()).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let request = |raw: String| {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let body = r#"{"difficulty":"easy","seed":1}"#;
+    let response = request(format!(
+        "POST /games HTTP/1.1\r\nHost: test\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    ));
+    assert!(
+        response.starts_with("HTTP/1.1 201 Created\r\n"),
+        "{response}"
+    );
+    assert!(response.contains("Content-Type: application/json"));
+
+    let response = request("GET /games/1 HTTP/1.1\r\nHost: test\r\n\r\n".to_string());
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    let json: Value = serde_json::from_str(response.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+    assert_eq!(json["max"], 10);
+    assert_eq!(json["history"], json!([]));
+}
+
+#[test]
+fn oversized_slow_or_malformed_requests_are_cut_off() {
+    let server = HttpServer::bind("127.0.0.1:0", api())
+        .unwrap()
+        .with_timeout(Duration::from_millis(200));
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let request = |raw: String| {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(raw.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    };
+
+    let response = request("POST /games HTTP/1.1\r\nContent-Length: lots\r\n\r\n{}".to_string());
+    assert!(
+        response.starts_with("HTTP/1.1 400 Bad Request\r\n"),
+        "{response}"
+    );
+
+    let response = request(format!(
+        "GET /games/1 HTTP/1.1\r\nX-Padding: {}\r\n\r\n",
+        "a".repeat(10_000)
+    ));
+    assert!(
+        response.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"),
+        "{response}"
+    );
+
+    // A client that never finishes its request is dropped, not waited on.
+    let started = Instant::now();
+    let response = request("GET /games/1 HTTP/1.1\r\n".to_string());
+    assert_eq!(response, "");
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,240 @@
+use guessing_game::cli::{Args, Command, Exit, USAGE};
+use guessing_game::game::Outcome;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{self, Stdio};
+use std::time::Duration;
+
+fn parse(line: &str) -> Result<Args, String> {
+    Args::parse(line.split_whitespace().map(String::from))
+}
+
+/// A fresh data directory for one test, removed when it goes out of scope.
+struct DataDir(PathBuf);
+
+impl DataDir {
+    fn new(name: &str) -> DataDir {
+        let dir = std::env::temp_dir().join(format!("guessing_game-cli-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        DataDir(dir)
+    }
+
+    /// Runs the real binary with `input` on stdin and this data dir,
+    /// returning its exit code and stdout.
+    fn run(&self, args: &[&str], input: &str) -> (i32, String) {
+        let mut child = process::Command::new(env!("CARGO_BIN_EXE_guessing_game"))
+            .args(args)
+            .env("GUESSING_GAME_DATA_DIR", &self.0)
+            .env("GUESSING_GAME_CONFIG", self.0.join("missing.toml"))
+            .env("LANG", "C")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        // The binary may exit without reading everything, closing the pipe.
+        let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+        let output = child.wait_with_output().unwrap();
+        (
+            output.status.code().unwrap(),
+            String::from_utf8(output.stdout).unwrap(),
+        )
+    }
+}
+
+impl Drop for DataDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn subcommands_are_recognised() {
+    assert_eq!(parse("").unwrap().command, Command::Play);
+    assert_eq!(parse("play --seed 1").unwrap().command, Command::Play);
+    assert_eq!(parse("bench").unwrap().command, Command::Bench);
+    assert_eq!(parse("daily --ascii").unwrap().command, Command::Daily);
+    assert_eq!(parse("serve").unwrap().command, Command::Serve);
+    assert_eq!(
+        parse("stats reset").unwrap().command,
+        Command::Stats { reset: true }
+    );
+    assert!(parse("frobnicate").is_err());
+}
+
+#[test]
+fn help_wins_over_other_arguments() {
+    assert_eq!(parse("help").unwrap().command, Command::Help);
+    assert_eq!(parse("--help").unwrap().command, Command::Help);
+    assert_eq!(parse("bench -h").unwrap().command, Command::Help);
+    assert_eq!(
+        parse("--seed 3 --help --bogus").unwrap().command,
+        Command::Help
+    );
+}
+
+#[test]
+fn usage_mentions_every_command() {
+    for command in [
+        "play",
+        "reverse",
+        "mastermind",
+        "speedrun",
+        "tournament",
+        "daily",
+        "adaptive",
+        "bench",
+        "serve",
+        "http",
+        "stats",
+        "replay",
+        "config",
+        "help",
+    ] {
+        assert!(
+            USAGE.contains(&format!("\n  {command} ")),
+            "{command} missing"
+        );
+    }
+}
+
+#[test]
+fn outcomes_map_to_exit_codes() {
+    let won = Outcome::Won {
+        attempts: 1,
+        score: 100,
+    };
+    let lost = Outcome::Lost {
+        attempts: 3,
+        secret_number: 7,
+    };
+    let quit = Outcome::Quit { attempts: 0 };
+
+    assert_eq!(Exit::from(won).code(), 0);
+    assert_eq!(Exit::from(lost).code(), 1);
+    assert_eq!(Exit::from(quit).code(), 3);
+    assert_eq!(Exit::Usage.code(), 2);
+    assert_eq!(Exit::Error.code(), 4);
+}
+
+#[test]
+fn binary_exit_status_reports_how_the_game_ended() {
+    let dir = DataDir::new("exit-status");
+    // Seed 24 hides 41 on the normal range.
+    let (code, stdout) = dir.run(&["--seed", "24", "--difficulty", "normal"], "50\n41\n");
+    assert_eq!(code, 0);
+    assert!(stdout.contains("You Win!"));
+
+    let (code, _) = dir.run(
+        &["--seed", "24", "--difficulty", "normal", "--attempts", "1"],
+        "50\n",
+    );
+    assert_eq!(code, 1);
+
+    let (code, _) = dir.run(&["--seed", "24", "--difficulty", "normal"], "50\n");
+    assert_eq!(code, 3);
+
+    let (code, _) = dir.run(&["--attempts", "0"], "");
+    assert_eq!(code, 2);
+
+    let (code, stdout) = dir.run(&["--help"], "");
+    assert_eq!(code, 0);
+    assert_eq!(stdout, USAGE);
+
+    let (code, _) = dir.run(&["replay", "/nonexistent/recording.json"], "");
+    assert_eq!(code, 4);
+}
+
+#[test]
+fn the_daily_puzzle_can_only_be_played_once() {
+    let dir = DataDir::new("daily");
+    let (code, stdout) = dir.run(&["daily", "--ascii"], "giveup\n");
+    assert_eq!(code, 1);
+    assert!(stdout.contains("Share your result:"));
+    assert!(stdout.contains("Guessing Game #"));
+
+    let (code, stdout) = dir.run(&["daily", "--ascii"], "50\n");
+    assert_eq!(code, 0);
+    assert!(stdout.contains("already played"));
+    assert!(!stdout.contains("You guessed"));
+}
+
+#[test]
+fn time_limits_must_be_positive_and_representable() {
+    let args = parse("--time-limit 1.5 --guess-time 2").unwrap();
+    assert_eq!(args.limits.per_game, Some(Duration::from_millis(1500)));
+    assert_eq!(args.limits.per_guess, Some(Duration::from_secs(2)));
+
+    for line in [
+        "--time-limit 0",
+        "--time-limit -3",
+        "--time-limit NaN",
+        "--time-limit inf",
+        "--time-limit 1e20",
+        "--guess-time 1e300",
+    ] {
+        let err = parse(line).unwrap_err();
+        assert!(
+            err.ends_with("must be a positive number of seconds"),
+            "{line}: {err}"
+        );
+    }
+}
+
+#[test]
+fn flags_a_command_would_ignore_are_rejected() {
+    for (line, error) in [
+        ("reverse --record f", "--record does not apply to reverse"),
+        (
+            "mastermind --record f",
+            "--record does not apply to mastermind",
+        ),
+        ("bench --hot-cold", "--hot-cold does not apply to bench"),
+        ("mastermind --tui", "--tui does not apply to mastermind"),
+        ("play --players a,b", "--players does not apply to play"),
+        ("daily --seed 3", "--seed does not apply to daily"),
+        (
+            "http --difficulty easy",
+            "--difficulty does not apply to http",
+        ),
+    ] {
+        assert_eq!(parse(line).unwrap_err(), error, "{line}");
+    }
+
+    assert!(parse("reverse --min 1 --max 20 --lang es").is_ok());
+    assert!(parse("mastermind --length 3 --attempts 5 --seed 1").is_ok());
+    assert!(parse("bench --difficulty hard --games 5 --help").is_ok());
+
+    let dir = DataDir::new("ignored-flags");
+    let (code, _) = dir.run(&["reverse", "--record", "f.json"], "");
+    assert_eq!(code, Exit::Usage.code());
+}
+
+#[test]
+fn modes_that_cannot_be_combined_are_rejected() {
+    let dir = DataDir::new("modes");
+    assert!(parse("--tui --hot-cold").is_err());
+    assert!(parse("--tui --guess-time 5").is_err());
+    assert!(parse("--tui --time-limit 30").is_err());
+    let args = parse("--hot-cold --time-limit 100").unwrap();
+    assert!(args.hot_cold && args.limits.is_set());
+
+    // Seed 24 hides 41.
+    let (code, stdout) = dir.run(
+        &[
+            "--seed",
+            "24",
+            "--difficulty",
+            "normal",
+            "--hot-cold",
+            "--time-limit",
+            "100",
+        ],
+        "1\n41\n",
+    );
+    assert_eq!(code, 0);
+    assert!(stdout.contains("You guessed 1\nFreezing!"), "{stdout}");
+    assert!(!stdout.contains("Too Small!"));
+}
@@ -0,0 +1,196 @@
+use guessing_game::cli::{Args, Command};
+use guessing_game::config::{config_path, Config, ConfigFile, Source};
+use guessing_game::difficulty::Difficulty;
+use guessing_game::i18n::Locale;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+fn args(line: &str) -> Args {
+    Args::parse(line.split_whitespace().map(String::from)).unwrap()
+}
+
+fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    move |name| vars.get(name).cloned()
+}
+
+fn file(source: &str) -> Option<ConfigFile> {
+    Some(ConfigFile::parse(source).unwrap())
+}
+
+#[test]
+fn parses_every_key() {
+    let parsed = ConfigFile::parse(
+        r#"
+        difficulty = "hard"
+        attempts = 12
+        language = "es"
+        colors = false
+        stats-path = "/tmp/stats.json"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        parsed,
+        ConfigFile {
+            difficulty: Some(Difficulty::Hard),
+            attempts: Some(12),
+            language: Some(Locale::Es),
+            colors: Some(false),
+            stats_path: Some(PathBuf::from("/tmp/stats.json")),
+        }
+    );
+
+    let custom = ConfigFile::parse("difficulty = { custom = { min = 1, max = 500 } }").unwrap();
+    assert_eq!(
+        custom.difficulty,
+        Some(Difficulty::Custom { min: 1, max: 500 })
+    );
+}
+
+#[test]
+fn rejects_unknown_keys_and_bad_values() {
+    assert!(ConfigFile::parse("difficulty = \"impossible\"").is_err());
+    assert!(ConfigFile::parse("atempts = 3").is_err());
+    assert!(ConfigFile::parse("attempts = 0").is_err());
+    assert!(ConfigFile::parse("difficulty = { custom = { min = 5, max = 1 } }").is_err());
+    assert!(ConfigFile::parse("language = \"fr\"").is_err());
+}
+
+#[test]
+fn command_line_beats_env_beats_file_beats_defaults() {
+    let file = file("difficulty = \"easy\"\nattempts = 3\nlanguage = \"hi\"");
+
+    let config = Config::resolve(&args(""), &env(&[]), None, file.clone()).unwrap();
+    assert_eq!(config.difficulty.value, Some(Difficulty::Easy));
+    assert_eq!(config.difficulty.source, Source::ConfigFile);
+    assert!(config.colors.value);
+    assert_eq!(config.colors.source, Source::Default);
+
+    let vars = env(&[
+        ("GUESSING_GAME_DIFFICULTY", "normal"),
+        ("GUESSING_GAME_ATTEMPTS", "5"),
+    ]);
+    let config = Config::resolve(&args(""), &vars, None, file.clone()).unwrap();
+    assert_eq!(config.difficulty.value, Some(Difficulty::Normal));
+    assert_eq!(
+        config.difficulty.source,
+        Source::Env("GUESSING_GAME_DIFFICULTY".to_string())
+    );
+    assert_eq!(config.attempts.value, Some(5));
+
+    let config =
+        Config::resolve(&args("--difficulty hard --attempts 9"), &vars, None, file).unwrap();
+    assert_eq!(config.difficulty.value, Some(Difficulty::Hard));
+    assert_eq!(config.difficulty.source, Source::CommandLine);
+    assert_eq!(config.attempts.value, Some(9));
+    assert_eq!(config.language.value, Locale::Hi);
+}
+
+#[test]
+fn defaults_when_nothing_is_set() {
+    let config = Config::resolve(&args(""), &env(&[]), None, None).unwrap();
+
+    assert_eq!(config.difficulty.value, None);
+    assert_eq!(config.attempts.value, None);
+    assert_eq!(config.language.value, Locale::En);
+    assert_eq!(config.language.source, Source::Default);
+    assert!(!config.found);
+}
+
+#[test]
+fn system_locale_only_applies_when_nothing_else_does() {
+    let vars = env(&[("LANG", "es_ES.UTF-8")]);
+    let config = Config::resolve(&args(""), &vars, None, None).unwrap();
+    assert_eq!(config.language.value, Locale::Es);
+    assert_eq!(config.language.source, Source::Env("LANG".to_string()));
+
+    let config = Config::resolve(&args(""), &vars, None, file("language = \"hi\"")).unwrap();
+    assert_eq!(config.language.value, Locale::Hi);
+
+    let vars = env(&[("LANG", "es_ES.UTF-8"), ("GUESSING_GAME_LANG", "en")]);
+    let config = Config::resolve(&args(""), &vars, None, file("language = \"hi\"")).unwrap();
+    assert_eq!(config.language.value, Locale::En);
+}
+
+#[test]
+fn no_color_turns_colors_off_unless_overridden() {
+    let vars = env(&[("NO_COLOR", "1")]);
+    let config = Config::resolve(&args(""), &vars, None, None).unwrap();
+    assert!(!config.colors.value);
+    assert_eq!(config.colors.source, Source::Env("NO_COLOR".to_string()));
+
+    let config = Config::resolve(&args("--color"), &vars, None, None).unwrap();
+    assert!(config.colors.value);
+}
+
+#[test]
+fn bad_environment_values_are_errors() {
+    let resolve = |vars: &[(&str, &str)]| Config::resolve(&args(""), &env(vars), None, None);
+
+    assert!(resolve(&[("GUESSING_GAME_ATTEMPTS", "lots")]).is_err());
+    assert!(resolve(&[("GUESSING_GAME_ATTEMPTS", "0")]).is_err());
+    assert!(resolve(&[("GUESSING_GAME_COLORS", "maybe")]).is_err());
+}
+
+#[test]
+fn config_file_location_follows_xdg() {
+    assert_eq!(
+        config_path(&env(&[("HOME", "/home/me")])),
+        Some(PathBuf::from("/home/me/.config/guessing_game/config.toml"))
+    );
+    assert_eq!(
+        config_path(&env(&[("HOME", "/home/me"), ("XDG_CONFIG_HOME", "/cfg")])),
+        Some(PathBuf::from("/cfg/guessing_game/config.toml"))
+    );
+    assert_eq!(
+        config_path(&env(&[("GUESSING_GAME_CONFIG", "/etc/gg.toml")])),
+        Some(PathBuf::from("/etc/gg.toml"))
+    );
+    assert_eq!(config_path(&env(&[])), None);
+}
+
+#[test]
+fn loads_from_disk_and_reports_sources() {
+    let dir = std::env::temp_dir().join(format!("guessing_game-config-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+
+    assert_eq!(ConfigFile::load(&path), Ok(None));
+    fs::write(&path, "attempts = 4\nstats-path = \"/tmp/s.json\"\n").unwrap();
+    let loaded = ConfigFile::load(&path).unwrap();
+
+    let config =
+        Config::resolve(&args("--lang es"), &env(&[]), Some(path.clone()), loaded).unwrap();
+    let mut report = Vec::new();
+    config.write_report(&mut report).unwrap();
+    let report = String::from_utf8(report).unwrap();
+
+    assert!(report.starts_with(&format!("Config file: {}\n", path.display())));
+    assert!(report.contains("attempts    = 4                        (config file)\n"));
+    assert!(report.contains("language    = es                       (command line)\n"));
+    assert!(report.contains("difficulty  = ask                      (built-in default)\n"));
+    assert_eq!(
+        config.stats_store().unwrap().path(),
+        PathBuf::from("/tmp/s.json")
+    );
+
+    fs::write(&path, "attempts = \"four\"").unwrap();
+    assert!(ConfigFile::load(&path).is_err());
+}
+
+#[test]
+fn config_show_and_color_flags_parse() {
+    assert_eq!(args("config").command, Command::Config);
+    assert_eq!(args("config show").command, Command::Config);
+    assert_eq!(args("--no-color").colors, Some(false));
+    assert_eq!(
+        args("--stats-path s.json").stats_path,
+        Some(PathBuf::from("s.json"))
+    );
+}
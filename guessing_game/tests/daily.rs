@@ -0,0 +1,179 @@
+use guessing_game::daily::{play_daily, DailyLog, DailyResult, Date, Puzzle};
+use guessing_game::difficulty::Difficulty;
+use std::fs;
+use std::io::ErrorKind;
+use std::process;
+
+fn date(text: &str) -> Date {
+    text.parse().unwrap()
+}
+
+#[test]
+fn dates_convert_to_and_from_day_numbers() {
+    assert_eq!(date("1970-01-01").days(), 0);
+    assert_eq!(date("2000-03-01").days(), 11_017);
+    assert_eq!(Date::from_days(19_782), date("2024-02-29"));
+    for days in -1000..30_000 {
+        assert_eq!(Date::from_days(days).days(), days);
+    }
+    assert_eq!(date("2024-02-29").to_string(), "2024-02-29");
+}
+
+#[test]
+fn impossible_dates_are_rejected() {
+    assert!("2023-02-29".parse::<Date>().is_err());
+    assert!("2024-13-01".parse::<Date>().is_err());
+    assert!("2024-1".parse::<Date>().is_err());
+    assert!("yesterday".parse::<Date>().is_err());
+    assert_eq!(Date::new(2024, 4, 31), None);
+}
+
+#[test]
+fn the_puzzle_depends_only_on_the_date() {
+    let day = date("2024-06-15");
+    assert_eq!(Puzzle::for_date(day), Puzzle::for_date(day));
+    assert_eq!(Puzzle::for_date(date("2024-01-01")).number, 1);
+    assert_eq!(Puzzle::for_date(day).number, 167);
+
+    let puzzles: Vec<Puzzle> = (0..60)
+        .map(|offset| Puzzle::for_date(Date::from_days(day.days() + offset)))
+        .collect();
+    for puzzle in &puzzles {
+        assert!(puzzle.difficulty.range().contains(&puzzle.secret));
+        assert_eq!(puzzle.max_attempts, puzzle.difficulty.optimal_guesses() + 1);
+        assert_eq!(puzzle.game().secret_number(), puzzle.secret);
+    }
+    // Consecutive days shouldn't all look alike.
+    assert!(puzzles
+        .windows(2)
+        .any(|pair| pair[0].secret != pair[1].secret));
+    assert!(puzzles
+        .windows(2)
+        .any(|pair| pair[0].difficulty != pair[1].difficulty));
+}
+
+/// Too small, too big, then the secret.
+fn winning_script(puzzle: &Puzzle) -> String {
+    let Difficulty::Custom { min, max } = puzzle.difficulty else {
+        panic!("daily puzzles use a custom range");
+    };
+    assert!(puzzle.secret > min && puzzle.secret < max);
+    format!("{}\n{}\n{}\n", min, max, puzzle.secret)
+}
+
+fn puzzle_with_room() -> Puzzle {
+    // A day whose secret isn't at either end of its range.
+    (0..)
+        .map(|offset| Puzzle::for_date(Date::from_days(date("2024-06-15").days() + offset)))
+        .find(|puzzle| {
+            let range = puzzle.difficulty.range();
+            puzzle.secret != *range.start() && puzzle.secret != *range.end()
+        })
+        .unwrap()
+}
+
+#[test]
+fn playing_records_each_verdict() {
+    let puzzle = puzzle_with_room();
+    let mut output = Vec::new();
+    let result = play_daily(&puzzle, winning_script(&puzzle).as_bytes(), &mut output).unwrap();
+
+    assert_eq!(
+        result,
+        DailyResult {
+            won: true,
+            attempts: 3,
+            hints: 0,
+            verdicts: "^v*".to_string(),
+        }
+    );
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(&format!("Daily puzzle #{}", puzzle.number)));
+    assert!(output.contains("You Win!"));
+}
+
+#[test]
+fn undone_guesses_stay_in_the_grid() {
+    let puzzle = puzzle_with_room();
+    let min = *puzzle.difficulty.range().start();
+    let script = format!("{min}\nundo\n{min}\ngiveup\n");
+    let result = play_daily(&puzzle, script.as_bytes(), Vec::new()).unwrap();
+
+    assert_eq!(result.attempts, 2);
+    assert_eq!(result.verdicts, "^^");
+}
+
+#[test]
+fn quitting_counts_as_a_loss() {
+    let puzzle = puzzle_with_room();
+    let result = play_daily(&puzzle, "hint\nquit\n".as_bytes(), Vec::new()).unwrap();
+    assert!(!result.won);
+    assert_eq!(result.hints, 1);
+    assert_eq!(result.verdicts, "");
+}
+
+#[test]
+fn shared_results_are_a_compact_grid() {
+    let puzzle = Puzzle::for_date(date("2024-06-15"));
+    let won = DailyResult {
+        won: true,
+        attempts: 7,
+        hints: 0,
+        verdicts: "^^vv^v*".to_string(),
+    };
+    let header = format!("Guessing Game #167 2024-06-15 7/{}", puzzle.max_attempts);
+    assert_eq!(won.share(&puzzle, true), format!("{header}\n^^vv^\nv*"));
+    assert_eq!(
+        won.share(&puzzle, false),
+        format!("{header}\n⬆️⬆️⬇️⬇️⬆️\n⬇️✅")
+    );
+
+    let lost = DailyResult {
+        won: false,
+        attempts: 2,
+        hints: 1,
+        verdicts: "v^".to_string(),
+    };
+    assert_eq!(
+        lost.share(&puzzle, true),
+        format!(
+            "Guessing Game #167 2024-06-15 X/{} (hints: 1)\nv^",
+            puzzle.max_attempts
+        )
+    );
+}
+
+#[test]
+fn the_log_remembers_played_days() {
+    let dir = std::env::temp_dir().join(format!("guessing_game-daily-{}", process::id()));
+    let path = dir.join("daily.json");
+    let _ = fs::remove_dir_all(&dir);
+
+    let day = date("2024-06-15");
+    let mut log = DailyLog::load(&path).unwrap();
+    assert_eq!(log.get(day), None);
+
+    log.start(day);
+    log.save(&path).unwrap();
+    assert_eq!(DailyLog::load(&path).unwrap().get(day), Some(&None));
+
+    let result = DailyResult {
+        won: true,
+        attempts: 1,
+        hints: 0,
+        verdicts: "*".to_string(),
+    };
+    log.finish(day, result.clone());
+    log.save(&path).unwrap();
+    let reloaded = DailyLog::load(&path).unwrap();
+    assert_eq!(reloaded.get(day), Some(&Some(result)));
+    assert_eq!(reloaded.get(date("2024-06-16")), None);
+
+    // A damaged log must not quietly reset and allow a replay.
+    fs::write(&path, "{ not json").unwrap();
+    assert_eq!(
+        DailyLog::load(&path).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
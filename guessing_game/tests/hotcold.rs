@@ -0,0 +1,171 @@
+use guessing_game::game::{Game, Outcome};
+use guessing_game::hotcold::{
+    play_hot_cold, play_hot_cold_timed, Band, Feedback, HotCold, Thresholds, Trend,
+};
+use guessing_game::input::ChannelLines;
+use guessing_game::timed::TimeLimits;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::mpsc;
+use std::time::Duration;
+
+fn thresholds() -> Thresholds {
+    Thresholds::new(1, 5, 10, 25).unwrap()
+}
+
+#[test]
+fn bands_include_their_upper_boundary() {
+    let t = thresholds();
+
+    assert_eq!(t.band(0), Band::Burning);
+    assert_eq!(t.band(1), Band::Burning);
+    assert_eq!(t.band(2), Band::Hot);
+    assert_eq!(t.band(5), Band::Hot);
+    assert_eq!(t.band(6), Band::Warm);
+    assert_eq!(t.band(10), Band::Warm);
+    assert_eq!(t.band(11), Band::Cold);
+    assert_eq!(t.band(25), Band::Cold);
+    assert_eq!(t.band(26), Band::Freezing);
+    assert_eq!(t.band(u64::MAX), Band::Freezing);
+}
+
+#[test]
+fn thresholds_must_increase() {
+    assert!(Thresholds::new(5, 5, 10, 25).is_err());
+    assert!(Thresholds::new(1, 5, 4, 25).is_err());
+    assert_eq!("1, 5,10,25".parse::<Thresholds>().unwrap(), thresholds());
+    assert!("1,5,10".parse::<Thresholds>().is_err());
+    assert!("a,b,c,d".parse::<Thresholds>().is_err());
+}
+
+#[test]
+fn default_thresholds_scale_with_the_range() {
+    assert_eq!(Thresholds::for_range_size(100), thresholds());
+    assert_eq!(
+        Thresholds::for_range_size(10_000),
+        Thresholds::new(100, 500, 1000, 2500).unwrap()
+    );
+    assert_eq!(
+        Thresholds::for_range_size(10),
+        Thresholds::new(1, 2, 3, 4).unwrap()
+    );
+}
+
+#[test]
+fn trend_compares_against_the_previous_guess() {
+    let mut hot_cold = HotCold::new(thresholds());
+
+    assert_eq!(
+        hot_cold.feedback(10, 50),
+        Feedback {
+            band: Band::Freezing,
+            trend: None
+        }
+    );
+    assert_eq!(hot_cold.feedback(45, 50).trend, Some(Trend::Warmer));
+    assert_eq!(hot_cold.feedback(55, 50).trend, Some(Trend::Same));
+    assert_eq!(hot_cold.feedback(70, 50).trend, Some(Trend::Colder));
+    assert_eq!(hot_cold.feedback(i64::MIN, i64::MAX).band, Band::Freezing);
+}
+
+#[test]
+fn undo_compares_the_next_guess_with_the_one_before() {
+    // Seed 24 hides 41.
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    let mut output = Vec::new();
+
+    play_hot_cold(
+        &mut game,
+        thresholds(),
+        "35\n1\nundo\n30\nundo\nundo\n90\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    let transcript = String::from_utf8(output).unwrap();
+    // 30 is colder than 35, not warmer than the taken-back 1.
+    assert!(transcript.contains("You guessed 30\nCold! Colder than last time.\n"));
+    // With nothing left to compare against, there is no trend.
+    assert!(transcript.contains("You guessed 90\nFreezing!\n"));
+}
+
+#[test]
+fn transcript_uses_bands_instead_of_directions() {
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    assert_eq!(game.secret_number(), 41);
+    let mut output = Vec::new();
+
+    let outcome = play_hot_cold(
+        &mut game,
+        thresholds(),
+        "1\n35\n40\n41\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(
+        outcome,
+        Outcome::Won {
+            attempts: 4,
+            score: 100
+        }
+    );
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("You guessed 1\nFreezing!\n"));
+    assert!(transcript.contains("You guessed 35\nWarm! Warmer than last time.\n"));
+    assert!(transcript.contains("You guessed 40\nBurning! Warmer than last time.\n"));
+    assert!(transcript.contains("You guessed 41\nYou Win!\n"));
+    assert!(!transcript.contains("Too"));
+}
+
+#[test]
+fn range_and_hint_do_not_give_away_the_direction() {
+    // Seed 24 hides 41.
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    let mut output = Vec::new();
+    play_hot_cold(
+        &mut game,
+        thresholds(),
+        "50\nrange\nhint\nhistory\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("The number is between 1 and 100.\n"));
+    assert!(transcript.contains("Hint: the number is between 1 and 50."));
+    assert!(transcript.contains("  50: Warm!\n"));
+}
+
+#[test]
+fn hot_cold_replies_work_against_the_clock() {
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    let (sender, receiver) = mpsc::channel();
+    sender.send(Ok("1".to_string())).unwrap();
+    let limits = TimeLimits {
+        per_game: None,
+        per_guess: Some(Duration::from_millis(50)),
+    };
+    let mut output = Vec::new();
+
+    let outcome = play_hot_cold_timed(
+        &mut game,
+        thresholds(),
+        limits,
+        ChannelLines::from_receiver(receiver),
+        &mut output,
+    )
+    .unwrap();
+    drop(sender);
+
+    assert_eq!(
+        outcome,
+        Outcome::Lost {
+            attempts: 1,
+            secret_number: 41
+        }
+    );
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("You guessed 1\nFreezing!\n"));
+    assert!(transcript.contains("Time's up! The secret number was 41."));
+}
@@ -0,0 +1,179 @@
+use guessing_game::cli::Args;
+use guessing_game::config::Source;
+use guessing_game::game::Game;
+use guessing_game::i18n::{locale, message, placeholders, plural, set_locale, Catalog, Locale};
+use guessing_game::mastermind::{play_mastermind, Rules};
+use guessing_game::play::play;
+use guessing_game::reverse::play_reverse;
+use guessing_game::stats::Stats;
+use guessing_game::strategy::{write_bench_report, BenchResult};
+use guessing_game::tui::{render, View};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::time::Duration;
+
+#[test]
+fn every_key_exists_in_every_bundle() {
+    let english = Catalog::for_locale(Locale::En);
+    for locale in Locale::ALL {
+        let catalog = Catalog::for_locale(locale);
+        assert_eq!(catalog.keys(), english.keys(), "keys differ in {locale}");
+    }
+}
+
+#[test]
+fn translations_use_the_same_placeholders() {
+    let english = Catalog::for_locale(Locale::En);
+    for locale in Locale::ALL {
+        let catalog = Catalog::for_locale(locale);
+        for key in english.keys() {
+            let mut expected = placeholders(english.get(key).unwrap());
+            let mut actual = placeholders(catalog.get(key).unwrap());
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(
+                actual, expected,
+                "placeholders differ for {key} in {locale}"
+            );
+        }
+    }
+}
+
+#[test]
+fn parses_bundles_and_rejects_bad_lines() {
+    let catalog = Catalog::parse("# comment\n\ngreeting = Hello, {name}!\n").unwrap();
+    assert_eq!(catalog.keys(), ["greeting"]);
+    assert_eq!(catalog.get("greeting"), Some("Hello, {name}!"));
+
+    assert!(Catalog::parse("no equals sign").is_err());
+    assert!(Catalog::parse("a = 1\na = 2").is_err());
+}
+
+#[test]
+fn parses_languages_and_posix_locales() {
+    assert_eq!("es".parse(), Ok(Locale::Es));
+    assert_eq!("es_MX.UTF-8".parse(), Ok(Locale::Es));
+    assert_eq!("hi-IN".parse(), Ok(Locale::Hi));
+    assert_eq!("C".parse(), Ok(Locale::En));
+    assert!("fr_FR".parse::<Locale>().is_err());
+}
+
+#[test]
+fn fills_placeholders_and_picks_plural_forms() {
+    assert_eq!(locale(), Locale::En);
+    assert_eq!(
+        message("game.range", &[("min", &1), ("max", &100)]),
+        "I'm thinking of a number between 1 and 100."
+    );
+    assert_eq!(plural("game.attempts-left", 1, &[]), "1 attempt left.");
+    assert_eq!(plural("game.attempts-left", 0, &[]), "0 attempts left.");
+
+    set_locale(Locale::Es);
+    assert_eq!(plural("game.attempts-left", 1, &[]), "Te queda 1 intento.");
+    assert_eq!(
+        plural("game.attempts-left", 3, &[]),
+        "Te quedan 3 intentos."
+    );
+
+    // Hindi treats zero as singular.
+    set_locale(Locale::Hi);
+    assert_eq!(plural("game.attempts-left", 0, &[]), "0 प्रयास बाकी है।");
+    assert_eq!(plural("game.attempts-left", 2, &[]), "2 प्रयास बाकी हैं।");
+}
+
+#[test]
+fn unknown_keys_fall_back_to_the_key() {
+    assert_eq!(message("no.such.key", &[]), "no.such.key");
+}
+
+#[test]
+fn plays_a_whole_game_in_spanish() {
+    set_locale(Locale::Es);
+    // Seed 24 hides 41.
+    let mut game = Game::new(StdRng::seed_from_u64(24)).with_max_attempts(Some(5));
+    let mut output = Vec::new();
+    play(&mut game, "50\nabc\n41\n".as_bytes(), &mut output).unwrap();
+
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.starts_with("¡Adivina el número!\n"));
+    assert!(transcript.contains("Tienes 5 intentos para encontrarlo.\n"));
+    assert!(transcript.contains("¡Demasiado grande!\nTe quedan 4 intentos.\n"));
+    assert!(transcript.contains("'abc' no es un número.\n"));
+    assert!(transcript.contains("¡Has ganado!\nLo has conseguido en 2 intentos"));
+}
+
+#[test]
+fn parses_the_lang_flag() {
+    let args = Args::parse(["--lang", "hi"].into_iter().map(String::from)).unwrap();
+    assert_eq!(args.lang, Some(Locale::Hi));
+    assert!(Args::parse(["--lang", "xx"].into_iter().map(String::from)).is_err());
+}
+
+#[test]
+fn every_mode_speaks_spanish() {
+    set_locale(Locale::Es);
+
+    let mut output = Vec::new();
+    play_reverse(1..=10, "higher\nmaybe\ncorrect\n".as_bytes(), &mut output).unwrap();
+    let reverse = String::from_utf8(output).unwrap();
+    assert!(reverse.starts_with("Piensa un número entre 1 y 10, ¡y lo adivinaré!\n"));
+    assert!(reverse.contains("Responde higher (mayor)"));
+    assert!(reverse.contains("He necesitado 2 intentos."));
+
+    let mut output = Vec::new();
+    play_mastermind(
+        &mut StdRng::seed_from_u64(1),
+        Rules::default(),
+        None,
+        "0\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    let mastermind = String::from_utf8(output).unwrap();
+    assert!(mastermind.starts_with("¡Descifra el código!"));
+
+    let view = View {
+        range: 1..=100,
+        possible: 1..=49,
+        history: &[(50, std::cmp::Ordering::Greater)],
+        attempts: 1,
+        attempts_left: None,
+        elapsed: Duration::from_secs(5),
+        input: "",
+        message: "",
+    };
+    let frame = render(&view, 30, 24);
+    assert!(frame.contains(&"Aún posible: de 1 a 49   (rango de 1 a 100)".to_string()));
+    assert!(frame.contains(&"Intentos: 1   Tiempo: 00:05".to_string()));
+    assert!(frame
+        .iter()
+        .any(|line| line.ends_with("¡Demasiado grande!")));
+
+    let mut output = Vec::new();
+    Stats::default().write_report(&mut output).unwrap();
+    let report = String::from_utf8(output).unwrap();
+    assert!(report.starts_with("Partidas jugadas: 0\n"));
+    assert!(report.contains("Media de intentos: -\n"));
+    assert_eq!(
+        Source::Env("GUESSING_GAME_LANG".to_string()).to_string(),
+        "variable GUESSING_GAME_LANG"
+    );
+
+    let mut output = Vec::new();
+    let failed = BenchResult {
+        name: "random".to_string(),
+        guesses: Vec::new(),
+        failed: 1,
+    };
+    write_bench_report(&[failed], &mut output).unwrap();
+    let report = String::from_utf8(output).unwrap();
+    assert!(report.starts_with("estrategia"));
+    assert!(report.contains("ninguna ronda resuelta"));
+    assert!(report.contains("Las rondas fallidas necesitaron más de"));
+    assert_eq!(
+        plural("replay.matches", 3, &[("seed", &7)]),
+        "La repetición coincide: 3 líneas de salida, semilla 7."
+    );
+
+    set_locale(Locale::En);
+}
@@ -0,0 +1,89 @@
+use guessing_game::input::{parse_command, parse_guess, read_guess, Command, InputError};
+
+const RANGE: std::ops::RangeInclusive<i64> = -10..=10;
+
+#[test]
+fn accepts_numbers_in_range_with_surrounding_whitespace() {
+    assert_eq!(parse_guess("  7\n", &RANGE).unwrap(), 7);
+    assert_eq!(parse_guess("-10", &RANGE).unwrap(), -10);
+    assert_eq!(parse_guess("+10", &RANGE).unwrap(), 10);
+}
+
+#[test]
+fn blank_lines_are_empty() {
+    assert!(matches!(parse_guess("\n", &RANGE), Err(InputError::Empty)));
+    assert!(matches!(
+        parse_guess(" \t ", &RANGE),
+        Err(InputError::Empty)
+    ));
+}
+
+#[test]
+fn words_are_not_numbers() {
+    match parse_guess("seven\n", &RANGE) {
+        Err(err @ InputError::NotANumber(_)) => {
+            assert_eq!(err.to_string(), "'seven' is not a number.")
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    assert!(matches!(
+        parse_guess("4.5", &RANGE),
+        Err(InputError::NotANumber(_))
+    ));
+}
+
+#[test]
+fn numbers_outside_the_range_are_out_of_range() {
+    match parse_guess("11", &RANGE) {
+        Err(err @ InputError::OutOfRange { .. }) => {
+            assert_eq!(
+                err.to_string(),
+                "11 is out of range! Guess between -10 and 10."
+            )
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+}
+
+#[test]
+fn numbers_too_large_for_i64_are_out_of_range() {
+    assert!(matches!(
+        parse_guess("99999999999999999999999", &RANGE),
+        Err(InputError::OutOfRange { .. })
+    ));
+    assert!(matches!(
+        parse_guess("-99999999999999999999999", &RANGE),
+        Err(InputError::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn closed_input_is_eof() {
+    let mut input = "3\n".as_bytes();
+
+    assert_eq!(read_guess(&mut input, &RANGE).unwrap(), 3);
+    assert!(matches!(
+        read_guess(&mut input, &RANGE),
+        Err(InputError::Eof)
+    ));
+}
+
+#[test]
+fn reserved_words_parse_as_commands() {
+    assert_eq!(parse_command("hint\n", &RANGE).unwrap(), Command::Hint);
+    assert_eq!(parse_command(" QUIT ", &RANGE).unwrap(), Command::Quit);
+    assert_eq!(parse_command("giveup", &RANGE).unwrap(), Command::GiveUp);
+    assert_eq!(parse_command("give up", &RANGE).unwrap(), Command::GiveUp);
+    assert_eq!(parse_command("History", &RANGE).unwrap(), Command::History);
+    assert_eq!(parse_command("range", &RANGE).unwrap(), Command::Range);
+    assert_eq!(parse_command("undo", &RANGE).unwrap(), Command::Undo);
+    assert_eq!(parse_command("-3", &RANGE).unwrap(), Command::Guess(-3));
+    assert!(matches!(
+        parse_command("hints", &RANGE),
+        Err(InputError::NotANumber(_))
+    ));
+    assert!(matches!(
+        parse_command("11", &RANGE),
+        Err(InputError::OutOfRange { .. })
+    ));
+}
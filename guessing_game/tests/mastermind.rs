@@ -0,0 +1,231 @@
+use guessing_game::i18n::{set_locale, Locale};
+use guessing_game::mastermind::{
+    play_mastermind, play_mastermind_timed, score, Code, CodeOutcome, Rules, Score, Solver,
+    MAX_HINT_CODES,
+};
+use guessing_game::timed::TimeLimits;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::time::Duration;
+
+fn code(text: &str) -> Code {
+    Code(text.bytes().map(|b| b - b'0').collect())
+}
+
+fn answer(exact: usize, misplaced: usize) -> Score {
+    Score { exact, misplaced }
+}
+
+#[test]
+fn scores_exact_and_misplaced_digits() {
+    assert_eq!(score(&code("1234"), &code("1234")), answer(4, 0));
+    assert_eq!(score(&code("1234"), &code("4321")), answer(0, 4));
+    assert_eq!(score(&code("1234"), &code("1356")), answer(1, 1));
+    assert_eq!(score(&code("1234"), &code("5678")), answer(0, 0));
+}
+
+#[test]
+fn repeated_digits_are_only_counted_once() {
+    assert_eq!(score(&code("1122"), &code("1212")), answer(2, 2));
+    assert_eq!(score(&code("1123"), &code("1111")), answer(2, 0));
+    assert_eq!(score(&code("1234"), &code("1111")), answer(1, 0));
+}
+
+#[test]
+fn rules_validate_their_shape() {
+    assert!(Rules::new(0, 10, false).is_err());
+    assert!(Rules::new(4, 1, true).is_err());
+    assert!(Rules::new(5, 4, false).is_err());
+    assert!(Rules::new(5, 4, true).is_ok());
+    assert_eq!(Rules::default().code_count(), 5040);
+    assert_eq!(Rules::new(4, 6, true).unwrap().code_count(), 1296);
+    assert_eq!(Rules::new(3, 4, false).unwrap().all_codes().len(), 24);
+}
+
+#[test]
+fn parses_guesses_and_explains_mistakes() {
+    let rules = Rules::new(4, 6, false).unwrap();
+
+    assert_eq!(rules.parse("0123\n").unwrap(), code("0123"));
+    assert_eq!(rules.parse("5 4 3 2").unwrap(), code("5432"));
+    assert_eq!(
+        rules.parse("0126").unwrap_err(),
+        "'6' is not a digit from 0 to 5."
+    );
+    assert_eq!(
+        rules.parse("012").unwrap_err(),
+        "A code has exactly 4 digits."
+    );
+    assert_eq!(
+        rules.parse("0120").unwrap_err(),
+        "No digit may appear twice."
+    );
+    assert!(Rules::new(4, 6, true).unwrap().parse("0000").is_ok());
+}
+
+#[test]
+fn random_codes_follow_the_rules() {
+    let rules = Rules::default();
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..200 {
+        let secret = rules.random_code(&mut rng);
+        assert_eq!(rules.parse(&secret.to_string()).unwrap(), secret);
+    }
+}
+
+#[test]
+fn knuth_solver_cracks_every_code_quickly() {
+    let rules = Rules::new(3, 5, true).unwrap();
+
+    for secret in rules.all_codes() {
+        let mut solver = Solver::new(rules).unwrap();
+        let mut guesses = 0;
+        loop {
+            let guess = solver.hint().unwrap();
+            guesses += 1;
+            let result = score(&secret, &guess);
+            if result.exact == 3 {
+                break;
+            }
+            solver.record(&guess, result);
+        }
+        assert!(guesses <= 5, "{secret} took {guesses}");
+    }
+}
+
+#[test]
+fn hints_are_not_offered_for_huge_code_spaces() {
+    let rules = Rules::new(8, 10, true).unwrap();
+    assert!(rules.code_count() > MAX_HINT_CODES as u64);
+    assert!(Solver::new(rules).is_none());
+}
+
+#[test]
+fn transcript_with_a_hint() {
+    let rules = Rules::new(3, 4, false).unwrap();
+    let secret = rules.random_code(&mut StdRng::seed_from_u64(6));
+    let script = format!("hint\n01\n{secret}\n");
+    let mut output = Vec::new();
+
+    let outcome = play_mastermind(
+        &mut StdRng::seed_from_u64(6),
+        rules,
+        None,
+        script.as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(
+        outcome,
+        CodeOutcome::Cracked {
+            attempts: 1,
+            hints: 1
+        }
+    );
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.starts_with("Break the code! It has 3 digits from 0 to 3, all different.\n"));
+    assert!(transcript.contains("Try "));
+    assert!(transcript.contains("A code has exactly 3 digits.\n"));
+    assert!(transcript.ends_with(&format!("You Win! You cracked {secret} in 1 guess.\n")));
+}
+
+#[test]
+fn running_out_of_attempts_reveals_the_code() {
+    let rules = Rules::new(2, 10, false).unwrap();
+    let secret = rules.random_code(&mut StdRng::seed_from_u64(2));
+    let wrong = if secret == code("98") { "89" } else { "98" };
+    let mut output = Vec::new();
+
+    let outcome = play_mastermind(
+        &mut StdRng::seed_from_u64(2),
+        rules,
+        Some(1),
+        format!("{wrong}\n").as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(
+        outcome,
+        CodeOutcome::Lost {
+            attempts: 1,
+            secret: secret.clone()
+        }
+    );
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .ends_with(&format!("Out of attempts! The code was {secret}.\n")));
+}
+
+fn transcript(rules: Rules, seed: u64, script: &str) -> (CodeOutcome, String) {
+    let mut output = Vec::new();
+    let outcome = play_mastermind(
+        &mut StdRng::seed_from_u64(seed),
+        rules,
+        None,
+        script.as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn prompt_words_work_as_in_the_number_game() {
+    let rules = Rules::new(2, 10, false).unwrap();
+    let secret = rules.random_code(&mut StdRng::seed_from_u64(3));
+    let wrong = if secret == code("98") { "89" } else { "98" };
+
+    let (outcome, text) = transcript(rules, 3, &format!("history\n{wrong}\nHISTORY\nquit\n"));
+    assert_eq!(outcome, CodeOutcome::Quit { attempts: 1 });
+    assert!(text.contains("No guesses yet.\n"));
+    let answer = score(&secret, &code(wrong));
+    assert!(text.contains(&format!("  {wrong}: {answer}\n")), "{text}");
+    assert!(text.ends_with("Goodbye!\n"));
+
+    let (outcome, text) = transcript(rules, 3, "give up\n");
+    assert_eq!(
+        outcome,
+        CodeOutcome::Lost {
+            attempts: 0,
+            secret: secret.clone()
+        }
+    );
+    assert!(text.ends_with(&format!("You gave up. The code was {secret}.\n")));
+}
+
+#[test]
+fn running_out_of_time_loses() {
+    let mut output = Vec::new();
+    let limits = TimeLimits {
+        per_game: Some(Duration::ZERO),
+        per_guess: None,
+    };
+    let outcome = play_mastermind_timed(
+        &mut StdRng::seed_from_u64(1),
+        Rules::default(),
+        None,
+        limits,
+        "0123\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+    assert!(matches!(outcome, CodeOutcome::Lost { attempts: 0, .. }));
+    assert!(String::from_utf8(output).unwrap().contains("Time's up!"));
+}
+
+#[test]
+fn messages_follow_the_locale() {
+    set_locale(Locale::Es);
+    let rules = Rules::new(3, 4, false).unwrap();
+    let (_, text) = transcript(rules, 6, "01\n0\n");
+    set_locale(Locale::En);
+
+    assert!(text.starts_with("¡Descifra el código! Tiene 3 dígitos del 0 al 3, todos distintos.\n"));
+    assert!(text.contains("Un código tiene exactamente 3 dígitos.\n"));
+    assert_eq!(
+        Rules::new(1, 4, false).unwrap().parse("01").unwrap_err(),
+        "A code has exactly 1 digit."
+    );
+}
@@ -0,0 +1,363 @@
+use guessing_game::cli::Args;
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{Game, Outcome, HINT_PENALTY};
+use guessing_game::play::{choose_difficulty, play};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn seeded_game(seed: u64) -> Game<StdRng> {
+    Game::new(StdRng::seed_from_u64(seed))
+}
+
+fn run(game: &mut Game<StdRng>, script: &str) -> String {
+    run_with_outcome(game, script).1
+}
+
+fn run_with_outcome(game: &mut Game<StdRng>, script: &str) -> (Outcome, String) {
+    let mut output = Vec::new();
+    let outcome = play(game, script.as_bytes(), &mut output).unwrap();
+    (outcome, String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn same_seed_gives_same_secret_sequence() {
+    let mut a = seeded_game(2024);
+    let mut b = seeded_game(2024);
+
+    for _ in 0..20 {
+        assert_eq!(a.secret_number(), b.secret_number());
+        a.next_round();
+        b.next_round();
+    }
+}
+
+#[test]
+fn seeded_transcript_is_exact() {
+    let mut game = seeded_game(24);
+    assert_eq!(game.secret_number(), 41);
+
+    let transcript = run(&mut game, "50\n10\n41\n");
+
+    assert_eq!(
+        transcript,
+        "Guess the number!\n\
+         I'm thinking of a number between 1 and 100.\n\
+         Please input your guess.\n\
+         You guessed 50\n\
+         Too Big!\n\
+         Please input your guess.\n\
+         You guessed 10\n\
+         Too Small!\n\
+         Please input your guess.\n\
+         You guessed 41\n\
+         You Win!\n\
+         You took 3 attempts (optimal is 7). Score: 100/100\n"
+    );
+}
+
+#[test]
+fn reports_too_small_and_too_big() {
+    let mut game = seeded_game(1);
+    let secret = game.secret_number();
+    let script = format!("{}\n{}\n{}\n", secret - 1, secret + 1, secret);
+
+    let transcript = run(&mut game, &script);
+
+    assert!(transcript.contains("Too Small!"));
+    assert!(transcript.contains("Too Big!"));
+    assert!(transcript.contains("You Win!\n"));
+}
+
+#[test]
+fn non_numeric_input_asks_again() {
+    let mut game = seeded_game(24);
+
+    let transcript = run(&mut game, "abc\n41\n");
+
+    assert_eq!(transcript.matches("Please input your guess.").count(), 2);
+    assert!(!transcript.contains("You guessed abc"));
+    assert!(transcript.contains("'abc' is not a number.\n"));
+    assert!(transcript.contains("You Win!\n"));
+}
+
+#[test]
+fn blank_input_asks_for_a_number() {
+    let mut game = seeded_game(24);
+
+    let transcript = run(&mut game, "\n   \n41\n");
+
+    assert_eq!(transcript.matches("Please type a number.").count(), 2);
+    assert!(transcript.contains("You Win!\n"));
+}
+
+#[test]
+fn end_of_input_quits_cleanly() {
+    let mut game = seeded_game(24);
+
+    let (outcome, transcript) = run_with_outcome(&mut game, "50\n");
+
+    assert_eq!(outcome, Outcome::Quit { attempts: 1 });
+    assert!(transcript.ends_with("No more input. The secret number was 41.\n"));
+}
+
+#[test]
+fn empty_input_quits_immediately() {
+    let mut game = seeded_game(24);
+
+    let (outcome, _) = run_with_outcome(&mut game, "");
+
+    assert_eq!(outcome, Outcome::Quit { attempts: 0 });
+}
+
+#[test]
+fn presets_have_documented_ranges() {
+    assert_eq!(Difficulty::Easy.range(), 1..=10);
+    assert_eq!(Difficulty::Normal.range(), 1..=100);
+    assert_eq!(Difficulty::Hard.range(), 1..=10_000);
+    assert_eq!(Difficulty::custom(-50, 50).unwrap().range(), -50..=50);
+    assert!(Difficulty::custom(5, 5).is_err());
+}
+
+#[test]
+fn secret_stays_inside_negative_custom_range() {
+    let difficulty = Difficulty::custom(-20, -10).unwrap();
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(3), difficulty);
+
+    for _ in 0..100 {
+        assert!((-20..=-10).contains(&game.secret_number()));
+        game.next_round();
+    }
+}
+
+#[test]
+fn out_of_range_guess_is_rejected_without_comparing() {
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(9), Difficulty::Easy);
+    let script = format!("0\n11\n{}\n", game.secret_number());
+
+    let transcript = run(&mut game, &script);
+
+    assert!(transcript.contains("0 is out of range! Guess between 1 and 10."));
+    assert!(transcript.contains("11 is out of range! Guess between 1 and 10."));
+    assert!(!transcript.contains("Too Small!"));
+    assert!(!transcript.contains("Too Big!"));
+    assert!(transcript.contains("You Win!\n"));
+}
+
+#[test]
+fn difficulty_flags_are_parsed() {
+    let parse = |line: &str| Args::parse(line.split_whitespace().map(String::from));
+
+    assert_eq!(
+        parse("--difficulty hard").unwrap().difficulty,
+        Some(Difficulty::Hard)
+    );
+    assert_eq!(
+        parse("--min -5 --max 5").unwrap().difficulty,
+        Some(Difficulty::Custom { min: -5, max: 5 })
+    );
+    assert_eq!(parse("").unwrap().difficulty, None);
+    assert!(parse("--min 1").is_err());
+    assert!(parse("--min 9 --max 1").is_err());
+    assert!(parse("--difficulty easy --min 1 --max 3").is_err());
+}
+
+#[test]
+fn difficulty_prompt_accepts_presets_and_custom_ranges() {
+    let mut output = Vec::new();
+    assert_eq!(
+        choose_difficulty("\n".as_bytes(), &mut output).unwrap(),
+        Some(Difficulty::Normal)
+    );
+    assert_eq!(
+        choose_difficulty("Easy\n".as_bytes(), &mut output).unwrap(),
+        Some(Difficulty::Easy)
+    );
+    assert_eq!(
+        choose_difficulty("nightmare\ncustom\n-100\n-1\n".as_bytes(), &mut output).unwrap(),
+        Some(Difficulty::Custom { min: -100, max: -1 })
+    );
+    assert_eq!(
+        choose_difficulty("custom\n5\n".as_bytes(), &mut output).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn optimal_guesses_is_ceil_log2_of_range_size() {
+    assert_eq!(Difficulty::Easy.optimal_guesses(), 4);
+    assert_eq!(Difficulty::Normal.optimal_guesses(), 7);
+    assert_eq!(Difficulty::Hard.optimal_guesses(), 14);
+    assert_eq!(Difficulty::custom(1, 2).unwrap().optimal_guesses(), 1);
+    assert_eq!(
+        Difficulty::custom(i64::MIN, i64::MAX)
+            .unwrap()
+            .optimal_guesses(),
+        64
+    );
+}
+
+#[test]
+fn running_out_of_attempts_reveals_the_secret() {
+    let mut game = seeded_game(24).with_max_attempts(Some(2));
+
+    let (outcome, transcript) = run_with_outcome(&mut game, "50\n10\n41\n");
+
+    assert_eq!(
+        outcome,
+        Outcome::Lost {
+            attempts: 2,
+            secret_number: 41
+        }
+    );
+    assert!(transcript.contains("You have 2 attempts to find it."));
+    assert!(transcript.contains("1 attempt left."));
+    assert!(transcript.ends_with("Out of attempts! The secret number was 41.\n"));
+    assert!(!transcript.contains("You Win!"));
+}
+
+#[test]
+fn winning_on_the_last_attempt_still_wins() {
+    let mut game = seeded_game(24).with_max_attempts(Some(2));
+
+    let (outcome, _) = run_with_outcome(&mut game, "50\n41\n");
+
+    assert_eq!(
+        outcome,
+        Outcome::Won {
+            attempts: 2,
+            score: 100
+        }
+    );
+}
+
+#[test]
+fn score_drops_below_optimal_play() {
+    let mut game = seeded_game(24);
+    let script: String = (1..=41).map(|n| format!("{n}\n")).collect();
+
+    let (outcome, transcript) = run_with_outcome(&mut game, &script);
+
+    // 7 optimal guesses out of 41 used.
+    assert_eq!(
+        outcome,
+        Outcome::Won {
+            attempts: 41,
+            score: 17
+        }
+    );
+    assert!(transcript.contains("Score: 17/100"));
+}
+
+#[test]
+fn out_of_range_guesses_do_not_use_attempts() {
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(9), Difficulty::Easy)
+        .with_max_attempts(Some(1));
+    let script = format!("0\n99\n{}\n", game.secret_number());
+
+    let (outcome, _) = run_with_outcome(&mut game, &script);
+
+    assert!(matches!(outcome, Outcome::Won { attempts: 1, .. }));
+}
+
+#[test]
+fn attempts_flag_rejects_zero() {
+    let parse = |line: &str| Args::parse(line.split_whitespace().map(String::from));
+
+    assert_eq!(parse("--attempts 5").unwrap().max_attempts, Some(5));
+    assert!(parse("--attempts 0").is_err());
+}
+
+#[test]
+fn hints_halve_the_possible_range_and_cost_points() {
+    // Seed 24 hides 41.
+    let mut game = seeded_game(24);
+
+    assert_eq!(game.hint(game.possible_range()), Some(1..=50));
+    game.guess(30);
+    assert_eq!(game.possible_range(), 31..=50);
+    assert_eq!(game.hint(game.possible_range()), Some(41..=50));
+    assert_eq!(game.hints(), 2);
+
+    game.guess(41);
+    assert_eq!(game.score(), 100 - 2 * HINT_PENALTY);
+
+    game.next_round();
+    assert_eq!(game.hints(), 0);
+    assert_eq!(game.hinted_range(), 1..=100);
+}
+
+#[test]
+fn no_hint_once_the_number_is_pinned_down() {
+    let mut game = Game::with_difficulty(
+        StdRng::seed_from_u64(1),
+        Difficulty::Custom { min: 5, max: 6 },
+    );
+    let secret = game.secret_number();
+
+    assert_eq!(game.hint(game.possible_range()), Some(secret..=secret));
+    assert_eq!(game.hint(game.possible_range()), None);
+    assert_eq!(game.hints(), 1);
+}
+
+#[test]
+fn in_game_commands_do_not_use_attempts() {
+    let mut game = seeded_game(24).with_max_attempts(Some(2));
+    let (outcome, transcript) =
+        run_with_outcome(&mut game, "history\n50\nrange\nhint\nhistory\n41\n");
+
+    assert_eq!(
+        outcome,
+        Outcome::Won {
+            attempts: 2,
+            score: 100 - HINT_PENALTY
+        }
+    );
+    assert!(transcript.contains("No guesses yet.\n"));
+    assert!(transcript.contains("The number is between 1 and 49.\n"));
+    assert!(transcript.contains("Hint: the number is between 26 and 49. That costs 10 points.\n"));
+    assert!(transcript.contains("  50: Too Big!\n"));
+}
+
+#[test]
+fn undo_takes_back_a_guess_but_not_the_attempt() {
+    let mut game = seeded_game(24).with_max_attempts(Some(3));
+    let (outcome, transcript) =
+        run_with_outcome(&mut game, "undo\n50\nundo\nrange\nhistory\n60\n70\n41\n");
+
+    assert_eq!(
+        outcome,
+        Outcome::Lost {
+            attempts: 3,
+            secret_number: 41
+        }
+    );
+    assert!(transcript.contains("No guess to take back.\n"));
+    assert!(transcript.contains("Took back 50. It still counts as an attempt.\n"));
+    assert!(transcript.contains("The number is between 1 and 100.\n"));
+    assert!(transcript.contains("No guesses yet.\n"));
+}
+
+#[test]
+fn giving_up_reveals_the_secret_and_loses() {
+    let mut game = seeded_game(24);
+    let (outcome, transcript) = run_with_outcome(&mut game, "50\ngiveup\n41\n");
+
+    assert_eq!(
+        outcome,
+        Outcome::Lost {
+            attempts: 1,
+            secret_number: 41
+        }
+    );
+    assert!(transcript.ends_with("You gave up. The secret number was 41.\n"));
+}
+
+#[test]
+fn quitting_keeps_the_secret() {
+    let mut game = seeded_game(24);
+    let (outcome, transcript) = run_with_outcome(&mut game, "quit\n41\n");
+
+    assert_eq!(outcome, Outcome::Quit { attempts: 0 });
+    assert!(transcript.ends_with("Goodbye!\n"));
+    assert!(!transcript.contains("41"));
+}
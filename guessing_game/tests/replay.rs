@@ -0,0 +1,152 @@
+use guessing_game::cli::{Args, Command};
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::Outcome;
+use guessing_game::hotcold::Thresholds;
+use guessing_game::i18n::Locale;
+use guessing_game::replay::{record, replay, Recording, Settings, Stream};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "guessing_game-replay-{}-{name}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn classic() -> Settings {
+    Settings {
+        difficulty: Difficulty::Normal,
+        max_attempts: None,
+        hot_cold: None,
+        locale: Locale::En,
+    }
+}
+
+fn parse(args: &[&str]) -> Result<Args, String> {
+    Args::parse(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn recording_captures_input_and_output_in_order() {
+    // Seed 24 hides 41 on the normal range.
+    let mut output = Vec::new();
+    let (outcome, recording) = record(24, classic(), "50\n41\n".as_bytes(), &mut output).unwrap();
+
+    assert_eq!(
+        outcome,
+        Outcome::Won {
+            attempts: 2,
+            score: 100
+        }
+    );
+    assert_eq!(recording.input(), "50\n41\n");
+    assert_eq!(recording.output(), String::from_utf8(output).unwrap());
+
+    let streams: Vec<Stream> = recording.events.iter().map(|event| event.stream).collect();
+    assert_eq!(
+        streams,
+        [
+            Stream::Output,
+            Stream::Input,
+            Stream::Output,
+            Stream::Input,
+            Stream::Output
+        ]
+    );
+    assert!(recording
+        .events
+        .windows(2)
+        .all(|pair| pair[0].at_ms <= pair[1].at_ms));
+}
+
+#[test]
+fn replaying_a_recording_matches() {
+    let settings = Settings {
+        difficulty: Difficulty::Normal,
+        max_attempts: Some(3),
+        hot_cold: Some(Thresholds::for_range_size(100)),
+        locale: Locale::Es,
+    };
+    let (outcome, recording) = record(7, settings, "abc\n90\n30\n".as_bytes(), io::sink()).unwrap();
+
+    let report = replay(&recording).unwrap();
+    assert_eq!(report.mismatch, None);
+    assert_eq!(report.outcome, outcome);
+    assert_eq!(report.output, recording.output());
+}
+
+#[test]
+fn tampered_output_is_reported_at_the_first_differing_line() {
+    let (_, mut recording) = record(24, classic(), "50\n41\n".as_bytes(), io::sink()).unwrap();
+    let last = recording
+        .events
+        .iter_mut()
+        .rfind(|event| event.stream == Stream::Output)
+        .unwrap();
+    last.text = last.text.replace("You Win!", "You Lose!");
+
+    let mismatch = replay(&recording).unwrap().mismatch.unwrap();
+    assert_eq!(mismatch.expected.as_deref(), Some("You Lose!"));
+    assert_eq!(mismatch.actual.as_deref(), Some("You Win!"));
+    assert_eq!(
+        recording.output().lines().nth(mismatch.line - 1),
+        Some("You Lose!")
+    );
+}
+
+#[test]
+fn a_different_seed_no_longer_matches() {
+    let (_, mut recording) = record(24, classic(), "50\n41\n".as_bytes(), io::sink()).unwrap();
+    recording.seed = 7;
+
+    assert!(replay(&recording).unwrap().mismatch.is_some());
+}
+
+#[test]
+fn recordings_round_trip_through_a_file() {
+    let dir = scratch_dir("round-trip");
+    let path = dir.join("session.json");
+    let (_, recording) = record(24, classic(), "50\n41\n".as_bytes(), io::sink()).unwrap();
+
+    recording.save(&path).unwrap();
+    assert_eq!(Recording::load(&path).unwrap(), recording);
+
+    fs::write(&path, "not json").unwrap();
+    let err = Recording::load(&path).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn unknown_format_versions_are_rejected() {
+    let dir = scratch_dir("version");
+    let path = dir.join("session.json");
+    let (_, mut recording) = record(24, classic(), "41\n".as_bytes(), io::sink()).unwrap();
+    recording.version = 99;
+    recording.save(&path).unwrap();
+
+    let err = Recording::load(&path).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn parses_record_flag_and_replay_subcommand() {
+    let args = parse(&["--record", "game.json", "--seed", "3"]).unwrap();
+    assert_eq!(args.record, Some(PathBuf::from("game.json")));
+
+    let args = parse(&["replay", "game.json"]).unwrap();
+    assert_eq!(
+        args.command,
+        Command::Replay {
+            path: PathBuf::from("game.json")
+        }
+    );
+
+    assert!(parse(&["replay"]).is_err());
+    assert!(parse(&["--record", "game.json", "--tui"]).is_err());
+    assert!(parse(&["--record", "game.json", "--time-limit", "30"]).is_err());
+}
@@ -0,0 +1,88 @@
+use guessing_game::reverse::{parse_answer, play_reverse, Cheating, Guesser, ReverseOutcome};
+use std::cmp::Ordering;
+
+/// Answers the guesser honestly for `secret`, returning the steps taken.
+fn solve(range: std::ops::RangeInclusive<i64>, secret: i64) -> u32 {
+    let mut guesser = Guesser::new(range);
+    loop {
+        let ordering = guesser.next_guess().cmp(&secret);
+        guesser.feedback(ordering).unwrap();
+        if ordering == Ordering::Equal {
+            return guesser.steps();
+        }
+    }
+}
+
+#[test]
+fn finds_every_number_within_the_optimal_bound() {
+    for secret in 1..=100 {
+        assert!(solve(1..=100, secret) <= 7, "secret {secret}");
+    }
+}
+
+#[test]
+fn handles_negative_and_extreme_ranges() {
+    assert!(solve(-1000..=-1, -1) <= 10);
+    // 2^64 candidates take at most 65 bisection steps.
+    assert!(solve(i64::MIN..=i64::MAX, i64::MIN) <= 65);
+    assert!(solve(i64::MIN..=i64::MAX, i64::MAX) <= 65);
+}
+
+#[test]
+fn contradicting_answers_are_cheating() {
+    let mut guesser = Guesser::new(1..=10);
+    assert_eq!(guesser.next_guess(), 5);
+    guesser.feedback(Ordering::Less).unwrap(); // higher than 5
+    assert_eq!(guesser.next_guess(), 8);
+    guesser.feedback(Ordering::Greater).unwrap(); // lower than 8
+    assert_eq!(guesser.next_guess(), 6);
+    guesser.feedback(Ordering::Less).unwrap(); // higher than 6
+    assert_eq!(guesser.next_guess(), 7);
+
+    assert_eq!(guesser.feedback(Ordering::Greater), Err(Cheating));
+}
+
+#[test]
+fn answers_map_to_orderings() {
+    assert_eq!(parse_answer("Higher\n"), Some(Ordering::Less));
+    assert_eq!(parse_answer("l"), Some(Ordering::Greater));
+    assert_eq!(parse_answer(" correct "), Some(Ordering::Equal));
+    assert_eq!(parse_answer("maybe"), None);
+}
+
+#[test]
+fn transcript_reports_steps() {
+    let mut output = Vec::new();
+
+    let outcome = play_reverse(
+        1..=10,
+        "higher\nwhat\nlower\ncorrect\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(
+        outcome,
+        ReverseOutcome::Solved {
+            number: 6,
+            steps: 3
+        }
+    );
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("Is it 5? (higher/lower/correct)"));
+    assert!(transcript.contains("Please answer higher, lower or correct."));
+    assert!(transcript.ends_with("Got it! Your number is 6. I needed 3 guesses.\n"));
+}
+
+#[test]
+fn transcript_calls_out_cheating_and_eof() {
+    let mut output = Vec::new();
+    let outcome = play_reverse(1..=2, "lower\n".as_bytes(), &mut output).unwrap();
+    assert_eq!(outcome, ReverseOutcome::Cheated { steps: 1 });
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("You're cheating"));
+
+    let outcome = play_reverse(1..=2, "".as_bytes(), Vec::new()).unwrap();
+    assert_eq!(outcome, ReverseOutcome::Quit);
+}
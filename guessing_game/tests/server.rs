@@ -0,0 +1,102 @@
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::Game;
+use guessing_game::server::{Client, Server};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::net::SocketAddr;
+use std::thread;
+
+/// Starts a server on a free localhost port, returning its address and the
+/// secret numbers it will use, in order.
+fn start(seed: u64) -> (SocketAddr, Vec<i64>) {
+    let mut preview = Game::with_difficulty(StdRng::seed_from_u64(seed), Difficulty::Easy);
+    let secrets = (0..3)
+        .map(|_| {
+            let secret = preview.secret_number();
+            preview.next_round();
+            secret
+        })
+        .collect();
+
+    let game = Game::with_difficulty(StdRng::seed_from_u64(seed), Difficulty::Easy);
+    let server = Server::bind("127.0.0.1:0", game).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+    (addr, secrets)
+}
+
+fn join(addr: SocketAddr, name: &str) -> Client {
+    let mut client = Client::connect(addr).unwrap();
+    assert!(client.recv().unwrap().ends_with(" 1 10"));
+    assert_eq!(client.request(&format!("NAME {name}")).unwrap(), "OK");
+    client
+}
+
+#[test]
+fn guesses_are_answered_low_high_win() {
+    let (addr, secrets) = start(1);
+    let mut client = join(addr, "ana");
+    let secret = secrets[0];
+
+    if secret > 1 {
+        assert_eq!(
+            client.request(&format!("GUESS {}", secret - 1)).unwrap(),
+            "LOW"
+        );
+    }
+    if secret < 10 {
+        assert_eq!(
+            client.request(&format!("GUESS {}", secret + 1)).unwrap(),
+            "HIGH"
+        );
+    }
+    assert_eq!(client.request(&format!("guess {secret}")).unwrap(), "WIN");
+    assert_eq!(client.recv().unwrap(), format!("WINNER ana {secret}"));
+    assert_eq!(client.recv().unwrap(), "ROUND 2 1 10");
+}
+
+#[test]
+fn winner_is_announced_to_every_player() {
+    let (addr, secrets) = start(2);
+    let mut ana = join(addr, "ana");
+    let mut bo = join(addr, "bo");
+
+    assert_eq!(bo.request(&format!("GUESS {}", secrets[0])).unwrap(), "WIN");
+
+    let announcement = format!("WINNER bo {}", secrets[0]);
+    assert_eq!(ana.recv().unwrap(), announcement);
+    assert_eq!(ana.recv().unwrap(), "ROUND 2 1 10");
+    assert_eq!(bo.recv().unwrap(), announcement);
+    assert_eq!(bo.recv().unwrap(), "ROUND 2 1 10");
+
+    // The next round uses the next secret from the same seeded sequence.
+    assert_eq!(
+        ana.request(&format!("GUESS {}", secrets[1])).unwrap(),
+        "WIN"
+    );
+    assert_eq!(ana.recv().unwrap(), format!("WINNER ana {}", secrets[1]));
+}
+
+#[test]
+fn malformed_lines_get_errors() {
+    let (addr, _) = start(3);
+    let mut client = join(addr, "cy");
+
+    assert_eq!(
+        client.request("GUESS 11").unwrap(),
+        "ERR 11 is out of range! Guess between 1 and 10."
+    );
+    assert_eq!(
+        client.request("GUESS x").unwrap(),
+        "ERR 'x' is not a number."
+    );
+    assert_eq!(
+        client.request("NAME two words").unwrap(),
+        "ERR name must be a single word"
+    );
+    assert_eq!(
+        client.request("DANCE").unwrap(),
+        "ERR unknown command 'DANCE'"
+    );
+    assert_eq!(client.request("QUIT").unwrap(), "BYE");
+}
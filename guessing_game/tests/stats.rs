@@ -0,0 +1,117 @@
+use guessing_game::cli::{Args, Command};
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::Outcome;
+use guessing_game::stats::{BestGame, Stats, StatsError, StatsStore};
+use std::fs;
+use std::path::PathBuf;
+
+/// A fresh, empty directory under the system temp dir for one test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("guessing_game-stats-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn won(attempts: u32) -> Outcome {
+    Outcome::Won {
+        attempts,
+        score: 50,
+    }
+}
+
+fn lost(attempts: u32) -> Outcome {
+    Outcome::Lost {
+        attempts,
+        secret_number: 1,
+    }
+}
+
+#[test]
+fn records_wins_losses_and_streaks() {
+    let mut stats = Stats::default();
+
+    stats.record(Difficulty::Normal, won(6));
+    stats.record(Difficulty::Normal, won(4));
+    stats.record(Difficulty::Normal, lost(10));
+    stats.record(Difficulty::Easy, won(2));
+
+    assert_eq!(stats.games_played, 4);
+    assert_eq!(stats.wins, 3);
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!(stats.best_streak, 2);
+    assert_eq!(stats.average_guesses(), Some(5.5));
+    assert_eq!(
+        stats.best_games["normal"],
+        BestGame {
+            attempts: 4,
+            score: 50
+        }
+    );
+    assert_eq!(stats.best_games["easy"].attempts, 2);
+}
+
+#[test]
+fn missing_file_loads_empty_stats() {
+    let store = StatsStore::new(scratch_dir("missing").join("stats.json"));
+
+    assert_eq!(store.load().unwrap(), Stats::default());
+}
+
+#[test]
+fn saved_stats_load_back_identically() {
+    let store = StatsStore::new(scratch_dir("roundtrip").join("nested").join("stats.json"));
+    let mut stats = Stats::default();
+    stats.record(Difficulty::Hard, won(12));
+    stats.record(Difficulty::custom(-5, 5).unwrap(), lost(3));
+
+    store.save(&stats).unwrap();
+
+    assert_eq!(store.load().unwrap(), stats);
+}
+
+#[test]
+fn corrupt_file_is_moved_aside() {
+    let dir = scratch_dir("corrupt");
+    let path = dir.join("stats.json");
+    fs::write(&path, "{ not json").unwrap();
+    let store = StatsStore::new(&path);
+
+    match store.load() {
+        Err(StatsError::Corrupt { backup }) => {
+            assert_eq!(fs::read_to_string(backup).unwrap(), "{ not json");
+        }
+        other => panic!("expected corrupt error, got {other:?}"),
+    }
+    assert_eq!(store.load().unwrap(), Stats::default());
+}
+
+#[test]
+fn reset_removes_the_file() {
+    let store = StatsStore::new(scratch_dir("reset").join("stats.json"));
+    let mut stats = Stats::default();
+    stats.record(Difficulty::Easy, won(1));
+    store.save(&stats).unwrap();
+
+    store.reset().unwrap();
+    store.reset().unwrap();
+
+    assert_eq!(store.load().unwrap(), Stats::default());
+}
+
+#[test]
+fn stats_subcommand_is_parsed() {
+    let parse = |line: &str| Args::parse(line.split_whitespace().map(String::from));
+
+    assert_eq!(parse("").unwrap().command, Command::Play);
+    assert_eq!(parse("play --seed 1").unwrap().command, Command::Play);
+    assert_eq!(
+        parse("stats").unwrap().command,
+        Command::Stats { reset: false }
+    );
+    assert_eq!(
+        parse("stats reset").unwrap().command,
+        Command::Stats { reset: true }
+    );
+}
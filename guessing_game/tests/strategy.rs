@@ -0,0 +1,130 @@
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::Game;
+use guessing_game::strategy::{
+    all_strategies, bench, solve, write_bench_report, BenchResult, BinarySearch, HumanLike, Linear,
+    RandomGuess, Strategy, MAX_GUESSES,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn guesses_for(strategy: &mut dyn Strategy, difficulty: Difficulty, seed: u64) -> Vec<u32> {
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(seed), difficulty);
+    let mut rng = StdRng::seed_from_u64(seed + 1);
+    (0..500)
+        .map(|_| {
+            let used = solve(strategy, &mut game, &mut rng).expect("solved");
+            game.next_round();
+            used
+        })
+        .collect()
+}
+
+#[test]
+fn binary_search_never_exceeds_the_optimal_bound() {
+    for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+        let bound = Difficulty::custom(0, difficulty.size() as i64)
+            .unwrap()
+            .optimal_guesses();
+        let worst = guesses_for(&mut BinarySearch::default(), difficulty, 5)
+            .into_iter()
+            .max()
+            .unwrap();
+        assert!(worst <= bound, "{difficulty}: {worst} > {bound}");
+    }
+}
+
+#[test]
+fn linear_needs_exactly_the_distance_from_the_bottom() {
+    let mut game =
+        Game::with_difficulty(StdRng::seed_from_u64(8), Difficulty::custom(-5, 5).unwrap());
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let used = solve(&mut Linear::default(), &mut game, &mut rng).unwrap();
+
+    assert_eq!(i64::from(used), game.secret_number() + 6);
+}
+
+#[test]
+fn every_strategy_always_finishes_inside_the_range_size() {
+    for mut strategy in all_strategies() {
+        let worst = guesses_for(strategy.as_mut(), Difficulty::Normal, 11)
+            .into_iter()
+            .max()
+            .unwrap();
+        assert!(worst <= 100, "{} took {worst}", strategy.name());
+    }
+    assert!(
+        guesses_for(&mut RandomGuess::default(), Difficulty::Easy, 1)
+            .iter()
+            .all(|&g| g <= 10)
+    );
+    assert!(guesses_for(&mut HumanLike::new(1.0), Difficulty::Easy, 1)
+        .iter()
+        .all(|&g| g <= 10));
+}
+
+#[test]
+fn bench_is_reproducible_for_a_seed() {
+    let first = bench(&mut all_strategies(), Difficulty::Normal, 300, 42);
+    let second = bench(&mut all_strategies(), Difficulty::Normal, 300, 42);
+
+    assert_eq!(first, second);
+    assert_eq!(
+        first.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+        ["binary", "human", "random", "linear"]
+    );
+    assert!(first[0].mean() < first[3].mean());
+}
+
+#[test]
+fn summary_statistics() {
+    let result = BenchResult {
+        name: "test".to_string(),
+        guesses: vec![1, 2, 2, 3, 7, 9],
+        failed: 0,
+    };
+
+    assert_eq!(result.mean(), 4.0);
+    assert_eq!(result.median(), 2.5);
+    assert_eq!(result.max(), 9);
+    assert_eq!(result.histogram(3), [(1, 3, 4), (4, 6, 0), (7, 9, 2)]);
+    assert_eq!(result.histogram(20)[0], (1, 1, 1));
+}
+
+#[test]
+fn report_lists_every_strategy() {
+    let results = bench(&mut all_strategies(), Difficulty::Easy, 50, 3);
+    let mut output = Vec::new();
+
+    write_bench_report(&results, &mut output).unwrap();
+
+    let report = String::from_utf8(output).unwrap();
+    for name in ["binary", "human", "random", "linear"] {
+        assert!(report.contains(&format!("{name}:\n")), "{report}");
+    }
+}
+
+#[test]
+fn huge_ranges_give_up_instead_of_running_forever() {
+    let difficulty = Difficulty::custom(1, 1_000_000_000_000).unwrap();
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(4), difficulty);
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(solve(&mut Linear::default(), &mut game, &mut rng), None);
+    assert_eq!(game.attempts(), MAX_GUESSES);
+
+    let results = bench(&mut all_strategies(), difficulty, 2, 9);
+    assert_eq!(results[0].failed, 0);
+    assert!(results[0].max() <= difficulty.optimal_guesses());
+    let linear = &results[3];
+    assert_eq!((linear.failed, linear.guesses.len()), (2, 0));
+    assert!(linear.mean().is_nan());
+
+    let mut output = Vec::new();
+    write_bench_report(&results, &mut output).unwrap();
+    let report = String::from_utf8(output).unwrap();
+    assert!(
+        report.contains("linear          -        -      -      2"),
+        "{report}"
+    );
+    assert!(report.contains("no round solved"), "{report}");
+}
@@ -0,0 +1,217 @@
+use guessing_game::difficulty::Difficulty;
+use guessing_game::game::{Game, Outcome};
+use guessing_game::input::{ChannelLines, Line, LineSource};
+use guessing_game::timed::{play_timed, speedrun, TimeLimits};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn timeout_is_the_tighter_of_both_limits() {
+    let none = TimeLimits::default();
+    assert!(!none.is_set());
+    assert_eq!(none.timeout(ms(500), ms(500)), None);
+
+    let per_guess = TimeLimits {
+        per_game: None,
+        per_guess: Some(ms(300)),
+    };
+    assert_eq!(per_guess.timeout(ms(10_000), ms(0)), Some(ms(300)));
+    assert_eq!(per_guess.timeout(ms(10_000), ms(200)), Some(ms(100)));
+    assert_eq!(per_guess.timeout(ms(10_000), ms(400)), Some(Duration::ZERO));
+
+    let both = TimeLimits {
+        per_game: Some(ms(1000)),
+        per_guess: Some(ms(300)),
+    };
+    assert_eq!(both.timeout(ms(100), ms(0)), Some(ms(300)));
+    assert_eq!(both.timeout(ms(900), ms(0)), Some(ms(100)));
+    assert_eq!(both.timeout(ms(2000), ms(0)), Some(Duration::ZERO));
+}
+
+#[test]
+fn channel_lines_time_out_while_waiting() {
+    let (sender, receiver) = mpsc::channel();
+    let mut lines = ChannelLines::from_receiver(receiver);
+
+    assert_eq!(lines.next_line(Some(ms(20))).unwrap(), Line::TimedOut);
+    sender.send(Ok("7".to_string())).unwrap();
+    assert_eq!(
+        lines.next_line(Some(ms(20))).unwrap(),
+        Line::Text("7".to_string())
+    );
+    drop(sender);
+    assert_eq!(lines.next_line(None).unwrap(), Line::Eof);
+}
+
+#[test]
+fn a_silent_player_loses_when_the_guess_timer_expires() {
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    let (sender, receiver) = mpsc::channel::<std::io::Result<String>>();
+    let limits = TimeLimits {
+        per_game: None,
+        per_guess: Some(ms(50)),
+    };
+    let mut output = Vec::new();
+
+    let started = Instant::now();
+    let outcome = play_timed(
+        &mut game,
+        limits,
+        ChannelLines::from_receiver(receiver),
+        &mut output,
+    )
+    .unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(
+        outcome,
+        Outcome::Lost {
+            attempts: 0,
+            secret_number: 41
+        }
+    );
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .ends_with("Time's up! The secret number was 41.\n"));
+    drop(sender);
+}
+
+#[test]
+fn commands_do_not_restart_the_guess_timer() {
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    let (sender, receiver) = mpsc::channel();
+    let limits = TimeLimits {
+        per_game: None,
+        per_guess: Some(ms(150)),
+    };
+    // Keeps sending filler well past the per-guess limit, then the answer.
+    let filler = std::thread::spawn(move || {
+        for line in ["range", "history", "hint", "oops", "range", "history"] {
+            std::thread::sleep(ms(40));
+            if sender.send(Ok(line.to_string())).is_err() {
+                return;
+            }
+        }
+        let _ = sender.send(Ok("41".to_string()));
+    });
+    let mut output = Vec::new();
+
+    let outcome = play_timed(
+        &mut game,
+        limits,
+        ChannelLines::from_receiver(receiver),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(
+        outcome,
+        Outcome::Lost {
+            attempts: 0,
+            secret_number: 41
+        }
+    );
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .ends_with("Time's up! The secret number was 41.\n"));
+    filler.join().unwrap();
+}
+
+#[test]
+fn the_game_clock_keeps_running_across_guesses() {
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    let (sender, receiver) = mpsc::channel();
+    sender.send(Ok("50".to_string())).unwrap();
+    let limits = TimeLimits {
+        per_game: Some(ms(80)),
+        per_guess: None,
+    };
+    let mut output = Vec::new();
+
+    let outcome = play_timed(
+        &mut game,
+        limits,
+        ChannelLines::from_receiver(receiver),
+        &mut output,
+    )
+    .unwrap();
+
+    assert!(matches!(outcome, Outcome::Lost { attempts: 1, .. }));
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("Too Big!\n("));
+    assert!(transcript.contains("s elapsed)\n"));
+    drop(sender);
+}
+
+#[test]
+fn winning_in_time_still_wins() {
+    let mut game = Game::new(StdRng::seed_from_u64(24));
+    let limits = TimeLimits {
+        per_game: Some(Duration::from_secs(30)),
+        per_guess: None,
+    };
+
+    let outcome = play_timed(&mut game, limits, "41\n".as_bytes(), Vec::new()).unwrap();
+
+    assert_eq!(
+        outcome,
+        Outcome::Won {
+            attempts: 1,
+            score: 100
+        }
+    );
+}
+
+#[test]
+fn speedrun_plays_every_round_with_the_next_secret() {
+    let mut preview = Game::with_difficulty(StdRng::seed_from_u64(3), Difficulty::Easy);
+    let mut script = String::new();
+    for _ in 0..3 {
+        script.push_str(&format!("{}\n", preview.secret_number()));
+        preview.next_round();
+    }
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(3), Difficulty::Easy);
+    let mut output = Vec::new();
+
+    let run = speedrun(
+        &mut game,
+        3,
+        TimeLimits::default(),
+        script.as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(run.wins(), 3);
+    assert_eq!(run.round_times.len(), 3);
+    assert!(run.total >= run.round_times.iter().sum());
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("Round 3 of 3\n"));
+    assert!(transcript.contains("Speedrun over: 3 of 3 rounds won in "));
+}
+
+#[test]
+fn quitting_ends_a_speedrun_early() {
+    let mut game = Game::with_difficulty(StdRng::seed_from_u64(3), Difficulty::Easy);
+
+    let mut output = Vec::new();
+
+    let run = speedrun(
+        &mut game,
+        5,
+        TimeLimits::default(),
+        "".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert_eq!(run.outcomes, [Outcome::Quit { attempts: 0 }]);
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("Speedrun over: 0 of 1 round won in "));
+}
@@ -0,0 +1,201 @@
+use guessing_game::cli::{Args, Command};
+use guessing_game::difficulty::Difficulty;
+use guessing_game::tournament::{
+    play_tournament, standings, write_standings, PlayerScore, Tournament, HANDOVER_LINES,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn names(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+fn score(name: &str, points: u32, wins: u32, attempts: u32) -> PlayerScore {
+    PlayerScore {
+        name: name.to_string(),
+        points,
+        wins,
+        attempts,
+    }
+}
+
+/// One guess each on a range of two: every game is decided in one turn.
+fn coin_flips(players: &[&str]) -> Tournament {
+    Tournament::new(names(players), Difficulty::Custom { min: 1, max: 2 })
+        .unwrap()
+        .with_max_attempts(Some(1))
+}
+
+#[test]
+fn needs_two_to_eight_distinct_players() {
+    let normal = Difficulty::Normal;
+    assert!(Tournament::new(names(&["Ann"]), normal).is_err());
+    assert!(Tournament::new(names(&["Ann", "ann"]), normal).is_err());
+    assert!(Tournament::new(names(&["Ann", " "]), normal).is_err());
+    assert!(Tournament::new(
+        names(&["1", "2", "3", "4", "5", "6", "7", "8", "9"]),
+        normal
+    )
+    .is_err());
+
+    let tournament = Tournament::new(names(&[" Ann ", "Bo"]), normal).unwrap();
+    assert_eq!(tournament.players(), ["Ann", "Bo"]);
+    assert_eq!(tournament.rounds(), 3);
+}
+
+#[test]
+fn tie_breakers_are_points_then_wins_then_attempts() {
+    let scores = [
+        score("Ann", 150, 2, 20),
+        score("Bo", 150, 3, 30),
+        score("Cy", 200, 2, 25),
+        score("Di", 150, 2, 18),
+        score("Ed", 150, 2, 20),
+    ];
+
+    let standings = standings(&scores);
+    let table: Vec<(usize, &str)> = standings
+        .iter()
+        .map(|standing| (standing.rank, standing.score.name.as_str()))
+        .collect();
+
+    assert_eq!(
+        table,
+        [(1, "Cy"), (2, "Bo"), (3, "Di"), (4, "Ann"), (4, "Ed")]
+    );
+}
+
+#[test]
+fn standings_table_marks_shared_places_and_the_winner() {
+    let scores = [score("Ann", 100, 1, 4), score("Bo", 100, 1, 4)];
+    let mut output = Vec::new();
+    write_standings(&standings(&scores), 1, &mut output).unwrap();
+    let table = String::from_utf8(output).unwrap();
+
+    assert!(table.starts_with("Standings after 1 round:\n"));
+    assert!(table.contains("   =1. Ann        100     1         4\n"));
+    assert!(table.ends_with("It's a tie between Ann, Bo!\n"));
+
+    let scores = [score("Ann", 100, 1, 4), score("Bo", 0, 0, 7)];
+    let mut output = Vec::new();
+    write_standings(&standings(&scores), 2, &mut output).unwrap();
+    let table = String::from_utf8(output).unwrap();
+    assert!(table.contains("    2. Bo           0     0         7\n"));
+    assert!(table.ends_with("Ann wins the tournament!\n"));
+}
+
+#[test]
+fn shared_secrets_give_everyone_the_same_game() {
+    let tournament = coin_flips(&["Ann", "Bo", "Cy"])
+        .with_rounds(4)
+        .with_shared_secret(true);
+    let mut output = Vec::new();
+    let result = play_tournament(
+        &mut StdRng::seed_from_u64(5),
+        &tournament,
+        "1\n".repeat(12).as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert!(!result.abandoned);
+    assert_eq!(result.rounds_played, 4);
+    // The same guess at the same secret: everyone ends level.
+    assert!(result.standings.iter().all(|standing| standing.rank == 1));
+    assert!(result
+        .standings
+        .iter()
+        .all(|standing| standing.score.attempts == 4));
+
+    let transcript = String::from_utf8(output).unwrap();
+    assert_eq!(
+        transcript
+            .matches("Everyone is after the same number")
+            .count(),
+        12
+    );
+    assert!(transcript.contains("=== Round 4 of 4 ===\n"));
+    assert!(transcript.contains("Cy, your turn."));
+}
+
+#[test]
+fn shared_turns_push_earlier_guesses_out_of_sight() {
+    let play = |tournament: &Tournament| {
+        let mut output = Vec::new();
+        play_tournament(
+            &mut StdRng::seed_from_u64(5),
+            tournament,
+            "1\n".repeat(2).as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    let shared = coin_flips(&["Ann", "Bo"])
+        .with_rounds(1)
+        .with_shared_secret(true);
+
+    let scrolled = play(&shared);
+    let (ann, bo) = scrolled.split_once("Bo, your turn.").unwrap();
+    assert!(ann.contains("Ann, your turn."));
+    assert!(ann.contains(&"\n".repeat(HANDOVER_LINES)));
+    assert!(!bo.contains("Ann, your turn."));
+
+    let cleared = play(&shared.clone().with_clear_screen(true));
+    assert!(cleared.contains("\x1b[2J"));
+    assert!(!cleared.contains(&"\n".repeat(HANDOVER_LINES)));
+
+    let separate = play(&coin_flips(&["Ann", "Bo"]).with_rounds(1));
+    assert!(!separate.contains(&"\n".repeat(HANDOVER_LINES)));
+}
+
+#[test]
+fn seeded_tournaments_repeat_exactly() {
+    let tournament = coin_flips(&["Ann", "Bo"]).with_rounds(5);
+    let run = || {
+        let mut output = Vec::new();
+        let result = play_tournament(
+            &mut StdRng::seed_from_u64(11),
+            &tournament,
+            "1\n".repeat(10).as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        (result, output)
+    };
+
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn quitting_abandons_the_tournament() {
+    let tournament = coin_flips(&["Ann", "Bo"]).with_rounds(3);
+    let mut output = Vec::new();
+    let result = play_tournament(
+        &mut StdRng::seed_from_u64(1),
+        &tournament,
+        "1\n1\n1\nquit\n".as_bytes(),
+        &mut output,
+    )
+    .unwrap();
+
+    assert!(result.abandoned);
+    assert_eq!(result.rounds_played, 1);
+    let transcript = String::from_utf8(output).unwrap();
+    assert!(transcript.contains("Tournament abandoned.\nStandings after 1 round:\n"));
+}
+
+#[test]
+fn tournament_flags_are_parsed_and_checked() {
+    let parse = |line: &str| Args::parse(line.split_whitespace().map(String::from));
+
+    let args = parse("tournament --players Ann,Bo,Cy --rounds 2 --shared").unwrap();
+    assert_eq!(args.command, Command::Tournament);
+    assert_eq!(args.players, ["Ann", "Bo", "Cy"]);
+    assert_eq!(args.rounds, Some(2));
+    assert!(args.shared_secret);
+
+    assert!(parse("tournament").is_err());
+    assert!(parse("tournament --players Ann").is_err());
+    assert!(parse("tournament --players Ann,Ann").is_err());
+}
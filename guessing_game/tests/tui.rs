@@ -0,0 +1,105 @@
+use guessing_game::game::{Game, Outcome};
+use guessing_game::tui::{interval_bar, render, submit, View};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cmp::Ordering;
+use std::time::Duration;
+
+#[test]
+fn typed_commands_work_in_the_full_screen_ui() {
+    // Seed 24 hides 41.
+    let mut game = Game::new(StdRng::seed_from_u64(24)).with_max_attempts(Some(5));
+
+    assert_eq!(submit(&mut game, "history").0, "No guesses yet.");
+    assert_eq!(submit(&mut game, "50"), ("Too Big!".to_string(), None));
+    assert_eq!(
+        submit(&mut game, "hint").0,
+        "Hint: the number is between 26 and 49. That costs 10 points."
+    );
+    assert_eq!(
+        submit(&mut game, "undo").0,
+        "Took back 50. It still counts as an attempt."
+    );
+    assert_eq!(
+        submit(&mut game, "range").0,
+        "The number is between 26 and 49."
+    );
+    assert_eq!(submit(&mut game, "ten").0, "'ten' is not a number.");
+    assert_eq!(game.attempts(), 1);
+    assert_eq!(
+        submit(&mut game, "GIVEUP"),
+        (
+            "You gave up. The secret number was 41.".to_string(),
+            Some(Outcome::Lost {
+                attempts: 1,
+                secret_number: 41
+            })
+        )
+    );
+}
+
+#[test]
+fn bar_marks_only_cells_still_possible() {
+    assert_eq!(interval_bar(&(1..=100), &(1..=100), 10), "##########");
+    assert_eq!(interval_bar(&(1..=100), &(51..=100), 10), ".....#####");
+    assert_eq!(interval_bar(&(1..=100), &(21..=29), 10), "..#.......");
+    assert_eq!(interval_bar(&(1..=10), &(7..=7), 5), "...#.");
+}
+
+#[test]
+fn bar_handles_ranges_narrower_than_the_bar() {
+    assert_eq!(interval_bar(&(1..=4), &(2..=2), 8), "..##....");
+}
+
+#[test]
+fn frame_shows_history_attempts_and_timer() {
+    let history = [(50, Ordering::Greater), (20, Ordering::Less)];
+    let view = View {
+        range: 1..=100,
+        possible: 21..=49,
+        history: &history,
+        attempts: 2,
+        attempts_left: Some(5),
+        elapsed: Duration::from_secs(75),
+        input: "3",
+        message: "Too Small!",
+    };
+
+    let lines = render(&view, 24, 24);
+
+    assert!(lines.contains(&"Still possible: 21 to 49   (range 1 to 100)".to_string()));
+    assert!(lines.contains(&"Attempts left: 5   Time: 01:15".to_string()));
+    assert!(lines.contains(&"   1.       50  Too Big!".to_string()));
+    assert!(lines.contains(&"   2.       20  Too Small!".to_string()));
+    assert!(lines.contains(&"> 3_".to_string()));
+    assert!(lines
+        .iter()
+        .any(|line| line.starts_with('[') && line.len() == 22));
+}
+
+#[test]
+fn frame_fits_short_terminals_keeping_the_latest_guesses() {
+    let history: Vec<(i64, Ordering)> = (1..=14).map(|guess| (guess, Ordering::Less)).collect();
+    let view = View {
+        range: 1..=10_000,
+        possible: 15..=10_000,
+        history: &history,
+        attempts: 14,
+        attempts_left: None,
+        elapsed: Duration::ZERO,
+        input: "15",
+        message: "",
+    };
+
+    let lines = render(&view, 40, 20);
+    assert_eq!(lines.len(), 20);
+    assert!(lines.contains(&"     ...".to_string()));
+    assert!(!lines.iter().any(|line| line.starts_with("   7.")));
+    assert!(lines.iter().any(|line| line.starts_with("   8.")));
+    assert!(lines.iter().any(|line| line.starts_with("  14.")));
+    assert!(lines.contains(&"> 15_".to_string()));
+
+    let tiny = render(&view, 40, 4);
+    assert_eq!(tiny.len(), 4);
+    assert_eq!(tiny[1], "> 15_");
+}
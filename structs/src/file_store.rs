@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::repository::{InMemoryUserRepository, RepositoryError, UserId, UserRepository};
+use crate::user::{Email, User, Username};
+
+/// The file layout written by this version. Bump it, and add a step to
+/// [`migrate`], whenever `User` gains a field.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The first line of the file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    /// The last id handed out, so deleted ids are not reused after reopening.
+    last_id: u64,
+}
+
+/// Every line after the header: one user with its id.
+#[derive(Debug, Serialize, Deserialize)]
+struct Record<U> {
+    id: u64,
+    #[serde(flatten)]
+    user: U,
+}
+
+/// Brings a record written by format `version` up to [`FORMAT_VERSION`],
+/// one version at a time.
+fn migrate(record: &mut Map<String, Value>, version: u32) {
+    if version < 1 {
+        // Format 0 files have no header and may leave out the account
+        // state, which format 1 always writes.
+        record.entry("active").or_insert(Value::Bool(true));
+        record.entry("sign_in_count").or_insert(Value::from(0));
+    }
+}
+
+/// A [`UserRepository`] kept in a JSON lines file: a header line, then one
+/// line per user. A file without the header line is read as format 0, just
+/// the user lines. Every change rewrites the whole file to a temporary file
+/// and renames it over the old one, so a crash mid-write leaves either the
+/// old or the new contents and never half of each.
+#[derive(Debug)]
+pub struct FileUserRepository {
+    path: PathBuf,
+    users: InMemoryUserRepository,
+}
+
+impl FileUserRepository {
+    /// Loads the users in `path`, or starts empty if it doesn't exist yet.
+    /// A file from an older format is migrated and rewritten straight away.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, RepositoryError> {
+        let path = path.into();
+        let (users, version) = match fs::read_to_string(&path) {
+            Ok(contents) => load(&contents)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                (InMemoryUserRepository::new(), FORMAT_VERSION)
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let repository = FileUserRepository { path, users };
+        if version < FORMAT_VERSION {
+            save(&repository.path, &repository.users)?;
+        }
+        Ok(repository)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    /// Applies `change` to a copy of the users and only keeps it once the
+    /// copy is safely on disk, so a failed write changes nothing.
+    fn change<T>(
+        &mut self,
+        change: impl FnOnce(&mut InMemoryUserRepository) -> Result<T, RepositoryError>,
+    ) -> Result<T, RepositoryError> {
+        let mut users = self.users.clone();
+        let value = change(&mut users)?;
+        save(&self.path, &users)?;
+        self.users = users;
+        Ok(value)
+    }
+}
+
+impl UserRepository for FileUserRepository {
+    fn create(&mut self, user: User) -> Result<UserId, RepositoryError> {
+        self.change(|users| users.create(user))
+    }
+
+    fn get(&self, id: UserId) -> Result<&User, RepositoryError> {
+        self.users.get(id)
+    }
+
+    fn find_by_username(&self, username: &Username) -> Result<(UserId, &User), RepositoryError> {
+        self.users.find_by_username(username)
+    }
+
+    fn find_by_email(&self, email: &Email) -> Result<(UserId, &User), RepositoryError> {
+        self.users.find_by_email(email)
+    }
+
+    fn update(&mut self, id: UserId, user: User) -> Result<(), RepositoryError> {
+        self.change(|users| users.update(id, user))
+    }
+
+    fn delete(&mut self, id: UserId) -> Result<User, RepositoryError> {
+        self.change(|users| users.delete(id))
+    }
+
+    fn list(&self) -> Vec<(UserId, &User)> {
+        self.users.list()
+    }
+}
+
+/// Parses a whole file, returning the users and the format it was in.
+fn load(contents: &str) -> Result<(InMemoryUserRepository, u32), RepositoryError> {
+    let mut users = InMemoryUserRepository::new();
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .peekable();
+
+    let Some(&(line, first)) = lines.peek() else {
+        return Ok((users, FORMAT_VERSION));
+    };
+    let corrupt = |line, reason: String| RepositoryError::Corrupt { line, reason };
+    let first: Map<String, Value> =
+        serde_json::from_str(first).map_err(|err| corrupt(line, format!("bad header: {err}")))?;
+    let header = if first.contains_key("version") {
+        lines.next();
+        let header: Header = serde_json::from_value(Value::Object(first))
+            .map_err(|err| corrupt(line, format!("bad header: {err}")))?;
+        // Format 0 had no header, so a header never says 0.
+        if header.version == 0 || header.version > FORMAT_VERSION {
+            return Err(corrupt(
+                line,
+                format!(
+                    "format version {} is not supported (this build reads up to {FORMAT_VERSION})",
+                    header.version
+                ),
+            ));
+        }
+        header
+    } else {
+        // The ids in use are reserved as the records are restored.
+        Header {
+            version: 0,
+            last_id: 0,
+        }
+    };
+    users.reserve_ids(header.last_id);
+
+    for (line, text) in lines {
+        let mut record: Map<String, Value> =
+            serde_json::from_str(text).map_err(|err| corrupt(line, err.to_string()))?;
+        migrate(&mut record, header.version);
+        let record: Record<User> = serde_json::from_value(Value::Object(record))
+            .map_err(|err| corrupt(line, err.to_string()))?;
+        let id = UserId(record.id);
+        if users.contains(id) {
+            return Err(corrupt(line, format!("user {id} appears twice")));
+        }
+        users
+            .restore(id, record.user)
+            .map_err(|err| corrupt(line, err.to_string()))?;
+    }
+    Ok((users, header.version))
+}
+
+/// Writes every user to a temporary file next to `path`, flushes it to
+/// disk and renames it into place. The rename is the commit point: an error
+/// before it leaves the old file and no temporary file behind, and nothing
+/// after it can fail the save.
+fn save(path: &Path, users: &InMemoryUserRepository) -> io::Result<()> {
+    let dir = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp = PathBuf::from(tmp_name);
+
+    let saved = write_users(&tmp, users).and_then(|()| fs::rename(&tmp, path));
+    if saved.is_err() {
+        let _ = fs::remove_file(&tmp);
+        return saved;
+    }
+    // The new contents are already in place, so reporting an error here
+    // would only leave the caller's state out of step with the file. At
+    // worst a crash now loses the rename and the old file comes back.
+    let _ = sync_dir(dir);
+    Ok(())
+}
+
+fn write_users(tmp: &Path, users: &InMemoryUserRepository) -> io::Result<()> {
+    let mut file = io::BufWriter::new(File::create(tmp)?);
+    let header = Header {
+        version: FORMAT_VERSION,
+        last_id: users.last_id(),
+    };
+    serde_json::to_writer(&mut file, &header)?;
+    writeln!(file)?;
+    for (id, user) in users.list() {
+        serde_json::to_writer(&mut file, &Record { id: id.0, user })?;
+        writeln!(file)?;
+    }
+    file.into_inner()
+        .map_err(io::IntoInnerError::into_error)?
+        .sync_all()
+}
+
+/// Flushes `dir` itself, so a rename into it survives a crash too.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories can't be opened as files here; the rename is as durable as
+/// the platform makes it.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
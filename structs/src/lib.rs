@@ -0,0 +1,3 @@
+pub mod file_store;
+pub mod repository;
+pub mod user;
@@ -1,10 +1,5 @@
-//Defining a struct
-struct User {
-    active: bool,
-    username: String,
-    email: String,
-    sign_in_count: u64,
-}
+use structs::repository::{InMemoryUserRepository, UserRepository};
+use structs::user::{Email, User, Username};
 
 #[derive(Debug)]
 struct Rectangle {
@@ -46,49 +41,78 @@ impl Rectangle {
 }
 
 fn main() {
-    //To use a struct after we have defined it, we create an instance of the struct by specifying concrete values for each fields
-    let user1 = User {
-        active: true,
-        username: String::from("someusername123"),
-        email: String::from("someone@example.com"),
-        sign_in_count: 1,
-    };
-    //We can access the values from a struct using dot notaion
-    println!("{}", user1.active);
-    //However, we cannot change the values using dot notation unless the entire struct is mutable
-    //We cannot change the above struct however, we can change the below struct
-    let mut user2 = User {
-        active: true,
-        username: String::from("someusername123"),
-        email: String::from("someone@example.com"),
-        sign_in_count: 1,
+    //A struct can be built by specifying concrete values for each field, but only where its fields are visible.
+    //User lives in the user module with private fields, so the only way to get one is through its constructors,
+    //which check the username and email first. Username and Email are separate types (newtypes wrapping a String),
+    //so passing the email where the username belongs is a compile error rather than a silent bug.
+    let user1 = match User::parse("someusername123", "someone@example.com") {
+        Ok(user) => user,
+        Err(err) => panic!("example user should be valid: {err}"),
     };
-    //Since the above struct is mut, so we can change the values using dot notation
-    user2.email = String::from("anotheremail@example.com");
-    //Note that the entire instance has to be mutable - we cannot have a few mutable fields
+    //Private fields are read through getters instead of dot notation
+    println!("{} <{}> active: {}", user1.username(), user1.email(), user1.active());
+
+    //Invalid input comes back as a UserError instead of a half-built User
+    match User::parse("admin", "admin@example.com") {
+        Ok(_) => println!("admin should have been refused"),
+        Err(err) => println!("Could not create user: {err}"),
+    }
+    if let Err(err) = Email::new("not-an-email") {
+        println!("Could not create email: {err}");
+    }
+
+    //We cannot change a value unless the entire instance is mutable - we cannot have a few mutable fields.
+    //With private fields, changes also go through methods, which keeps the email valid.
+    let mut user2 = build_user(
+        Username::new("someusername123").expect("valid username"),
+        Email::new("someone@example.com").expect("valid email"),
+    );
+    user2.set_email(Email::new("anotheremail@example.com").expect("valid email"));
+    user2.sign_in();
+    println!(
+        "{} has signed in {} times",
+        user2.username(),
+        user2.sign_in_count()
+    );
 
-    //Field Init Shorthand
-    build_user(String::from("someusername123"), String::from("someone@example.com"));
+    //A repository keeps many users instead of one local variable each, and refuses a second user
+    //with the same username or (ignoring case) the same email
+    let mut users = InMemoryUserRepository::new();
+    let id = users.create(user1).expect("empty repository");
+    if let Err(err) = users.create(user2) {
+        println!("Could not store user2: {err}");
+    }
+    match users.find_by_email(&Email::new("SOMEONE@example.com").expect("valid email")) {
+        Ok((found, user)) => println!("{} is user {found} (created as {id})", user.username()),
+        Err(err) => println!("{err}"),
+    }
 
     //Struct Update Syntax
-    //Every other value will be kept the same as user1 except email which will be changed
-    let _user3 = User {
-        email: String::from("another@example.com"),
-        //The following code should come at last to specify remaining elements are same as user1
-        ..user1
+    //Every other value will be kept the same as rect except width which will be changed.
+    //It needs the fields to be visible, so it works on Rectangle but not on User from outside the user module.
+    let rect = Rectangle {
+        width: 10,
+        height: 20,
+    };
+    let _wider = Rectangle {
+        width: 40,
+        //The following code should come at last to specify remaining elements are same as rect
+        ..rect
     };
-    //IMPORTANT NOTE - in the above code, we can no longer user user1 because username field of user1 has been 'MOVED' (check ownership) to user3
-    //However, if we gave a new value of email and username for user3 and used update syntax only for active and sign_in_count,
-    //user1 would still be valid since both active and sign_in_count types apply 'COPY' trait (which is not applied by email and username)
+    //IMPORTANT NOTE - fields that do not implement 'COPY' (like String) are 'MOVED' (check ownership) by update syntax,
+    //so the original could no longer be used. Both fields of Rectangle are u32, which is 'COPY', so rect is still valid here.
+    println!("rect is still {}x{}", rect.width, rect.height);
 
     //Tupled Structs
     struct Color(i32, i32, i32);
     struct Point(i32, i32, i32);
-    let _black = Color(0, 0, 0);
-    let _origin = Point(0, 0, 0);
+    let black = Color(0, 0, 0);
+    let origin = Point(0, 0, 0);
     //Each struct we define is a different type although they are made up of same type (i32 in the above case)
     //A function which takes Color as input cannot be passed Point, it will throw an error
     //To access an individual value in tupled structs, you use a '.' followed by index
+    println!("black is ({}, {}, {})", black.0, black.1, black.2);
+    println!("origin is ({}, {}, {})", origin.0, origin.1, origin.2);
 
     //Unit-like structs
     struct AlwaysEqual;
@@ -126,12 +150,8 @@ fn main() {
     let _sq1 = Rectangle::square(30);
 }
 
-fn build_user(email: String, username: String) -> User{
-    User{
-        active: true,
-        //we can use the following shorthand instead of username: username
-        username,
-        email,
-        sign_in_count: 1,
-    }
+//Field Init Shorthand: the parameters have the same names as the constructor's, so we can write username instead of username: username.
+//Taking Username and Email instead of two Strings means the arguments can't be swapped by accident.
+fn build_user(username: Username, email: Email) -> User {
+    User::new(username, email)
 }
@@ -0,0 +1,240 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::user::{Email, User, Username};
+
+/// Identifies a stored user. Ids are handed out by the repository and never
+/// reused, so one stays valid across username and email changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UserId(pub u64);
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/// What a failed lookup was looking for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Lookup {
+    Id(UserId),
+    Username(Username),
+    Email(Email),
+}
+
+impl fmt::Display for Lookup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lookup::Id(id) => write!(f, "id {id}"),
+            Lookup::Username(username) => write!(f, "username '{username}'"),
+            Lookup::Email(email) => write!(f, "email '{email}'"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RepositoryError {
+    NotFound(Lookup),
+    /// Another user already has this username.
+    UsernameTaken(Username),
+    /// Another user already has this email, ignoring case.
+    EmailTaken(Email),
+    /// The backing storage could not be read or written.
+    Io(io::Error),
+    /// The backing storage holds something that isn't a valid user list.
+    Corrupt {
+        line: usize,
+        reason: String,
+    },
+}
+
+impl fmt::Display for RepositoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RepositoryError::NotFound(lookup) => write!(f, "no user with {lookup}"),
+            RepositoryError::UsernameTaken(username) => {
+                write!(f, "username '{username}' is already taken")
+            }
+            RepositoryError::EmailTaken(email) => {
+                write!(f, "email '{email}' is already in use")
+            }
+            RepositoryError::Io(err) => write!(f, "user storage failed: {err}"),
+            RepositoryError::Corrupt { line, reason } => {
+                write!(f, "user storage is corrupt at line {line}: {reason}")
+            }
+        }
+    }
+}
+
+impl Error for RepositoryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RepositoryError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RepositoryError {
+    fn from(err: io::Error) -> Self {
+        RepositoryError::Io(err)
+    }
+}
+
+/// Somewhere users are kept. Usernames are unique exactly; emails are
+/// unique ignoring case.
+pub trait UserRepository {
+    /// Stores a new user and returns its id.
+    fn create(&mut self, user: User) -> Result<UserId, RepositoryError>;
+
+    fn get(&self, id: UserId) -> Result<&User, RepositoryError>;
+
+    fn find_by_username(&self, username: &Username) -> Result<(UserId, &User), RepositoryError>;
+
+    /// Finds the user whose email matches `email` ignoring case.
+    fn find_by_email(&self, email: &Email) -> Result<(UserId, &User), RepositoryError>;
+
+    /// Replaces the user stored under `id`, which may change its username
+    /// or email as long as they stay unique.
+    fn update(&mut self, id: UserId, user: User) -> Result<(), RepositoryError>;
+
+    /// Removes the user, returning what was stored.
+    fn delete(&mut self, id: UserId) -> Result<User, RepositoryError>;
+
+    /// Every user, oldest first.
+    fn list(&self) -> Vec<(UserId, &User)>;
+}
+
+/// A [`UserRepository`] that lives only as long as the process.
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryUserRepository {
+    users: BTreeMap<UserId, User>,
+    by_username: HashMap<Username, UserId>,
+    /// Keyed by [`Email::normalized`].
+    by_email: HashMap<String, UserId>,
+    /// The last id handed out.
+    last_id: u64,
+}
+
+impl InMemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+
+    /// The last id handed out, which stays used even once its user is
+    /// deleted.
+    pub(crate) fn last_id(&self) -> u64 {
+        self.last_id
+    }
+
+    /// Puts back a user under the id it was given earlier, as when loading
+    /// from disk, and makes sure `id` and everything before it is never
+    /// handed out again.
+    pub(crate) fn restore(&mut self, id: UserId, user: User) -> Result<(), RepositoryError> {
+        self.check_unique(&user, None)?;
+        self.reserve_ids(id.0);
+        self.index(id, &user);
+        self.users.insert(id, user);
+        Ok(())
+    }
+
+    pub(crate) fn reserve_ids(&mut self, last_id: u64) {
+        self.last_id = self.last_id.max(last_id);
+    }
+
+    pub(crate) fn contains(&self, id: UserId) -> bool {
+        self.users.contains_key(&id)
+    }
+
+    /// Fails if `user`'s username or email belongs to anyone but `id`.
+    fn check_unique(&self, user: &User, id: Option<UserId>) -> Result<(), RepositoryError> {
+        if let Some(&owner) = self.by_username.get(user.username()) {
+            if Some(owner) != id {
+                return Err(RepositoryError::UsernameTaken(user.username().clone()));
+            }
+        }
+        if let Some(&owner) = self.by_email.get(&user.email().normalized()) {
+            if Some(owner) != id {
+                return Err(RepositoryError::EmailTaken(user.email().clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn index(&mut self, id: UserId, user: &User) {
+        self.by_username.insert(user.username().clone(), id);
+        self.by_email.insert(user.email().normalized(), id);
+    }
+
+    fn unindex(&mut self, user: &User) {
+        self.by_username.remove(user.username());
+        self.by_email.remove(&user.email().normalized());
+    }
+}
+
+impl UserRepository for InMemoryUserRepository {
+    fn create(&mut self, user: User) -> Result<UserId, RepositoryError> {
+        self.check_unique(&user, None)?;
+        self.last_id += 1;
+        let id = UserId(self.last_id);
+        self.index(id, &user);
+        self.users.insert(id, user);
+        Ok(id)
+    }
+
+    fn get(&self, id: UserId) -> Result<&User, RepositoryError> {
+        self.users
+            .get(&id)
+            .ok_or(RepositoryError::NotFound(Lookup::Id(id)))
+    }
+
+    fn find_by_username(&self, username: &Username) -> Result<(UserId, &User), RepositoryError> {
+        let id = *self
+            .by_username
+            .get(username)
+            .ok_or_else(|| RepositoryError::NotFound(Lookup::Username(username.clone())))?;
+        Ok((id, &self.users[&id]))
+    }
+
+    fn find_by_email(&self, email: &Email) -> Result<(UserId, &User), RepositoryError> {
+        let id = *self
+            .by_email
+            .get(&email.normalized())
+            .ok_or_else(|| RepositoryError::NotFound(Lookup::Email(email.clone())))?;
+        Ok((id, &self.users[&id]))
+    }
+
+    fn update(&mut self, id: UserId, user: User) -> Result<(), RepositoryError> {
+        if !self.users.contains_key(&id) {
+            return Err(RepositoryError::NotFound(Lookup::Id(id)));
+        }
+        self.check_unique(&user, Some(id))?;
+        let old = self.users.insert(id, user.clone()).expect("checked above");
+        self.unindex(&old);
+        self.index(id, &user);
+        Ok(())
+    }
+
+    fn delete(&mut self, id: UserId) -> Result<User, RepositoryError> {
+        let user = self
+            .users
+            .remove(&id)
+            .ok_or(RepositoryError::NotFound(Lookup::Id(id)))?;
+        self.unindex(&user);
+        Ok(user)
+    }
+
+    fn list(&self) -> Vec<(UserId, &User)> {
+        self.users.iter().map(|(&id, user)| (id, user)).collect()
+    }
+}
@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Longest username accepted, in characters.
+pub const MAX_USERNAME_LEN: usize = 32;
+
+/// Longest email address accepted, in bytes (the limit mail servers use).
+pub const MAX_EMAIL_LEN: usize = 254;
+
+/// Names that would pass for the system or its staff, compared ignoring case.
+pub const RESERVED_USERNAMES: [&str; 6] = [
+    "admin",
+    "administrator",
+    "root",
+    "system",
+    "support",
+    "moderator",
+];
+
+/// Why a username or email was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserError {
+    EmptyUsername,
+    UsernameTooLong {
+        len: usize,
+    },
+    /// Only letters, digits, `_`, `-` and `.` are allowed.
+    InvalidUsernameChar(char),
+    ReservedUsername(String),
+    InvalidEmail(String),
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserError::EmptyUsername => write!(f, "username cannot be empty"),
+            UserError::UsernameTooLong { len } => write!(
+                f,
+                "username is {len} characters long; the limit is {MAX_USERNAME_LEN}"
+            ),
+            UserError::InvalidUsernameChar(c) => {
+                write!(f, "username cannot contain '{c}'")
+            }
+            UserError::ReservedUsername(name) => write!(f, "username '{name}' is reserved"),
+            UserError::InvalidEmail(email) => write!(f, "'{email}' is not a valid email address"),
+        }
+    }
+}
+
+impl Error for UserError {}
+
+/// A username that has passed validation. Being its own type, it can't be
+/// passed where an [`Email`] is expected, or the other way round. It is
+/// validated again when deserialized.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Username(String);
+
+impl Username {
+    /// Checks `name` is non-empty, at most [`MAX_USERNAME_LEN`] characters,
+    /// made of letters, digits, `_`, `-` and `.`, and not reserved.
+    pub fn new(name: &str) -> Result<Username, UserError> {
+        if name.is_empty() {
+            return Err(UserError::EmptyUsername);
+        }
+        let len = name.chars().count();
+        if len > MAX_USERNAME_LEN {
+            return Err(UserError::UsernameTooLong { len });
+        }
+        if let Some(c) = name
+            .chars()
+            .find(|c| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        {
+            return Err(UserError::InvalidUsernameChar(c));
+        }
+        if RESERVED_USERNAMES
+            .iter()
+            .any(|reserved| reserved.eq_ignore_ascii_case(name))
+        {
+            return Err(UserError::ReservedUsername(name.to_string()));
+        }
+        Ok(Username(name.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for Username {
+    type Err = UserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Username::new(s)
+    }
+}
+
+impl TryFrom<String> for Username {
+    type Error = UserError;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Username::new(&name)
+    }
+}
+
+impl From<Username> for String {
+    fn from(username: Username) -> String {
+        username.0
+    }
+}
+
+impl fmt::Display for Username {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// An email address that has passed a sanity check. This is not full RFC
+/// 5322 parsing, only enough to catch typos and swapped arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Email(String);
+
+impl Email {
+    /// Checks `address` looks like `local@domain.tld`: one `@`, no spaces,
+    /// something before it, and a dotted domain after it.
+    pub fn new(address: &str) -> Result<Email, UserError> {
+        let invalid = || UserError::InvalidEmail(address.to_string());
+        if address.len() > MAX_EMAIL_LEN || address.chars().any(char::is_whitespace) {
+            return Err(invalid());
+        }
+        let (local, domain) = address.split_once('@').ok_or_else(invalid)?;
+        let domain_ok = domain.contains('.')
+            && !domain.contains('@')
+            && domain.split('.').all(|label| !label.is_empty());
+        if local.is_empty() || !domain_ok {
+            return Err(invalid());
+        }
+        Ok(Email(address.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The address in lower case, for comparing addresses the way mail
+    /// providers do in practice.
+    pub fn normalized(&self) -> String {
+        self.0.to_lowercase()
+    }
+}
+
+impl FromStr for Email {
+    type Err = UserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Email::new(s)
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = UserError;
+
+    fn try_from(address: String) -> Result<Self, Self::Error> {
+        Email::new(&address)
+    }
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> String {
+        email.0
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A user account. The fields are private so an account can only hold a
+/// validated username and email.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    active: bool,
+    username: Username,
+    email: Email,
+    sign_in_count: u64,
+}
+
+impl User {
+    /// A new, active account that has signed in once.
+    pub fn new(username: Username, email: Email) -> User {
+        User {
+            active: true,
+            username,
+            email,
+            sign_in_count: 1,
+        }
+    }
+
+    /// Validates both strings and builds the account, failing on the first
+    /// bad one.
+    pub fn parse(username: &str, email: &str) -> Result<User, UserError> {
+        Ok(User::new(Username::new(username)?, Email::new(email)?))
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn username(&self) -> &Username {
+        &self.username
+    }
+
+    pub fn email(&self) -> &Email {
+        &self.email
+    }
+
+    pub fn sign_in_count(&self) -> u64 {
+        self.sign_in_count
+    }
+
+    pub fn sign_in(&mut self) {
+        self.sign_in_count += 1;
+    }
+
+    pub fn set_email(&mut self, email: Email) {
+        self.email = email;
+    }
+
+    pub fn deactivate(&mut self) {
+        self.active = false;
+    }
+}
@@ -0,0 +1,189 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use structs::file_store::{FileUserRepository, FORMAT_VERSION};
+use structs::repository::{RepositoryError, UserId, UserRepository};
+use structs::user::{Email, User, Username};
+
+/// A fresh scratch directory per test.
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("structs-{name}-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn user(username: &str, email: &str) -> User {
+    User::parse(username, email).unwrap()
+}
+
+#[test]
+fn reopening_reconstructs_the_same_users() {
+    let dir = scratch("reopen");
+    let path = dir.join("users.jsonl");
+
+    let mut repo = FileUserRepository::open(&path).unwrap();
+    assert!(repo.is_empty());
+    let ferris = repo.create(user("ferris", "ferris@example.com")).unwrap();
+    let corro = repo.create(user("corro", "corro@example.com")).unwrap();
+    let gone = repo.create(user("gone", "gone@example.com")).unwrap();
+    let mut changed = repo.get(ferris).unwrap().clone();
+    changed.sign_in();
+    changed.set_email(Email::new("ferris@rust-lang.org").unwrap());
+    repo.update(ferris, changed).unwrap();
+    repo.delete(gone).unwrap();
+
+    let reopened = FileUserRepository::open(&path).unwrap();
+    assert_eq!(reopened.list(), repo.list());
+    assert_eq!(
+        reopened.get(ferris).unwrap().email().as_str(),
+        "ferris@rust-lang.org"
+    );
+    assert_eq!(reopened.get(ferris).unwrap().sign_in_count(), 2);
+    let (id, _) = reopened
+        .find_by_email(&Email::new("CORRO@example.com").unwrap())
+        .unwrap();
+    assert_eq!(id, corro);
+
+    // Uniqueness still holds, and deleted ids are not handed out again.
+    let mut reopened = reopened;
+    assert!(matches!(
+        reopened.create(user("ferris", "new@example.com")),
+        Err(RepositoryError::UsernameTaken(_))
+    ));
+    assert_eq!(
+        reopened.create(user("new", "new@example.com")).unwrap(),
+        UserId(4)
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_rejected_change_leaves_the_file_alone() {
+    let dir = scratch("rejected");
+    let path = dir.join("users.jsonl");
+    let mut repo = FileUserRepository::open(&path).unwrap();
+    repo.create(user("ferris", "ferris@example.com")).unwrap();
+    let before = fs::read_to_string(&path).unwrap();
+
+    assert!(repo.create(user("crab", "FERRIS@example.com")).is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), before);
+    assert_eq!(repo.len(), 1);
+    assert!(!dir.join("users.jsonl.tmp").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_failed_write_keeps_the_old_state() {
+    let dir = scratch("failed");
+    // A directory where the file should be makes the final rename fail.
+    let path = dir.join("users.jsonl");
+    let mut repo = FileUserRepository::open(&path).unwrap();
+    fs::create_dir(&path).unwrap();
+    fs::write(path.join("blocker"), "").unwrap();
+
+    assert!(matches!(
+        repo.create(user("ferris", "ferris@example.com")),
+        Err(RepositoryError::Io(_))
+    ));
+    assert!(repo.is_empty());
+    assert!(!dir.join("users.jsonl.tmp").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn files_in_the_current_format_load_untouched() {
+    let dir = scratch("current");
+    let path = dir.join("users.jsonl");
+    let contents = format!(
+        "{{\"version\":{FORMAT_VERSION},\"last_id\":2}}\n\
+         {{\"id\":2,\"active\":true,\"username\":\"ferris\",\
+         \"email\":\"ferris@example.com\",\"sign_in_count\":3}}\n"
+    );
+    fs::write(&path, &contents).unwrap();
+
+    let repo = FileUserRepository::open(&path).unwrap();
+    let (id, ferris) = repo
+        .find_by_username(&Username::new("ferris").unwrap())
+        .unwrap();
+    assert_eq!(id, UserId(2));
+    assert_eq!(ferris.sign_in_count(), 3);
+
+    // Nothing to migrate, so nothing is rewritten.
+    assert_eq!(fs::read_to_string(&path).unwrap(), contents);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn headerless_files_are_migrated_and_written_back() {
+    let dir = scratch("migrate");
+    let path = dir.join("users.jsonl");
+    // Format 0: no header, and the account state may be left out.
+    fs::write(
+        &path,
+        concat!(
+            "{\"id\":3,\"username\":\"ferris\",\"email\":\"ferris@example.com\"}\n",
+            "{\"id\":5,\"active\":false,\"username\":\"corro\",",
+            "\"email\":\"corro@example.com\",\"sign_in_count\":7}\n",
+        ),
+    )
+    .unwrap();
+
+    let mut repo = FileUserRepository::open(&path).unwrap();
+    let (id, ferris) = repo
+        .find_by_username(&Username::new("ferris").unwrap())
+        .unwrap();
+    assert_eq!(id, UserId(3));
+    assert!(ferris.active());
+    assert_eq!(ferris.sign_in_count(), 0);
+    let corro = repo.get(UserId(5)).unwrap();
+    assert!(!corro.active());
+    assert_eq!(corro.sign_in_count(), 7);
+
+    // The upgrade is written back straight away, in the current format.
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(contents.starts_with(&format!("{{\"version\":{FORMAT_VERSION},\"last_id\":5}}\n")));
+    assert!(contents
+        .contains("\"username\":\"ferris\",\"email\":\"ferris@example.com\",\"sign_in_count\":0"));
+    assert_eq!(FileUserRepository::open(&path).unwrap().list(), repo.list());
+    assert_eq!(
+        repo.create(user("new", "new@example.com")).unwrap(),
+        UserId(6)
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn damaged_files_are_reported_with_the_line() {
+    let dir = scratch("corrupt");
+    let path = dir.join("users.jsonl");
+    let header = format!("{{\"version\":{FORMAT_VERSION},\"last_id\":2}}\n");
+    let ferris = "{\"id\":1,\"active\":true,\"username\":\"ferris\",\
+                  \"email\":\"ferris@example.com\",\"sign_in_count\":1}\n";
+
+    for (contents, bad_line) in [
+        (format!("{header}{ferris}{{\"id\":2,"), 3),
+        (format!("{header}{ferris}{ferris}"), 3),
+        (
+            format!("{header}{}", ferris.replace("ferris@", "ferris at ")),
+            2,
+        ),
+        (
+            format!("{header}{}", ferris.replace("\"ferris\"", "\"root\"")),
+            2,
+        ),
+        (
+            format!("{header}{}", ferris.replace(",\"sign_in_count\":1", "")),
+            2,
+        ),
+        ("{\"version\":99,\"last_id\":0}\n".to_string(), 1),
+        ("{\"version\":0,\"last_id\":0}\n".to_string(), 1),
+    ] {
+        fs::write(&path, &contents).unwrap();
+        match FileUserRepository::open(&path) {
+            Err(RepositoryError::Corrupt { line, .. }) => assert_eq!(line, bad_line, "{contents}"),
+            other => panic!("expected corruption for {contents}, got {other:?}"),
+        }
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,126 @@
+use structs::repository::{
+    InMemoryUserRepository, Lookup, RepositoryError, UserId, UserRepository,
+};
+use structs::user::{Email, User, Username};
+
+fn user(username: &str, email: &str) -> User {
+    User::parse(username, email).unwrap()
+}
+
+fn username(name: &str) -> Username {
+    Username::new(name).unwrap()
+}
+
+fn email(address: &str) -> Email {
+    Email::new(address).unwrap()
+}
+
+#[test]
+fn created_users_can_be_found_by_any_key() {
+    let mut repo = InMemoryUserRepository::new();
+    let ferris = repo.create(user("ferris", "ferris@example.com")).unwrap();
+    let corro = repo.create(user("corro", "corro@example.com")).unwrap();
+    assert_ne!(ferris, corro);
+    assert_eq!(repo.len(), 2);
+
+    assert_eq!(repo.get(ferris).unwrap().username().as_str(), "ferris");
+    let (id, found) = repo.find_by_username(&username("corro")).unwrap();
+    assert_eq!(id, corro);
+    assert_eq!(found.email().as_str(), "corro@example.com");
+    let (id, _) = repo.find_by_email(&email("FERRIS@Example.COM")).unwrap();
+    assert_eq!(id, ferris);
+
+    let listed: Vec<&str> = repo
+        .list()
+        .into_iter()
+        .map(|(_, user)| user.username().as_str())
+        .collect();
+    assert_eq!(listed, ["ferris", "corro"]);
+}
+
+#[test]
+fn usernames_and_emails_must_be_unique() {
+    let mut repo = InMemoryUserRepository::new();
+    repo.create(user("ferris", "ferris@example.com")).unwrap();
+
+    let err = repo
+        .create(user("ferris", "other@example.com"))
+        .unwrap_err();
+    assert!(matches!(err, RepositoryError::UsernameTaken(ref name) if name.as_str() == "ferris"));
+    assert_eq!(err.to_string(), "username 'ferris' is already taken");
+
+    let err = repo.create(user("crab", "Ferris@EXAMPLE.com")).unwrap_err();
+    assert!(matches!(err, RepositoryError::EmailTaken(_)));
+
+    // Usernames only clash exactly.
+    repo.create(user("Ferris", "big.ferris@example.com"))
+        .unwrap();
+    assert_eq!(repo.len(), 2);
+}
+
+#[test]
+fn updates_keep_the_indexes_in_step() {
+    let mut repo = InMemoryUserRepository::new();
+    let ferris = repo.create(user("ferris", "ferris@example.com")).unwrap();
+    repo.create(user("corro", "corro@example.com")).unwrap();
+
+    let mut changed = repo.get(ferris).unwrap().clone();
+    changed.set_email(email("crab@example.com"));
+    changed.sign_in();
+    repo.update(ferris, changed).unwrap();
+    assert_eq!(repo.get(ferris).unwrap().sign_in_count(), 2);
+    assert_eq!(
+        repo.find_by_email(&email("crab@example.com")).unwrap().0,
+        ferris
+    );
+    assert!(repo.find_by_email(&email("ferris@example.com")).is_err());
+
+    // Changing only the case of your own email is not a clash.
+    repo.update(ferris, user("ferris", "CRAB@example.com"))
+        .unwrap();
+
+    // Renaming frees the old name.
+    repo.update(ferris, user("crab", "crab@example.com"))
+        .unwrap();
+    assert!(repo.find_by_username(&username("ferris")).is_err());
+    assert_eq!(repo.find_by_username(&username("crab")).unwrap().0, ferris);
+
+    let err = repo
+        .update(ferris, user("corro", "crab@example.com"))
+        .unwrap_err();
+    assert!(matches!(err, RepositoryError::UsernameTaken(_)));
+    assert_eq!(repo.get(ferris).unwrap().username().as_str(), "crab");
+}
+
+#[test]
+fn deleted_users_are_gone_and_free_their_keys() {
+    let mut repo = InMemoryUserRepository::new();
+    let ferris = repo.create(user("ferris", "ferris@example.com")).unwrap();
+    let deleted = repo.delete(ferris).unwrap();
+    assert_eq!(deleted.username().as_str(), "ferris");
+    assert!(repo.is_empty());
+
+    assert!(matches!(
+        repo.get(ferris),
+        Err(RepositoryError::NotFound(Lookup::Id(id))) if id == ferris
+    ));
+    assert!(matches!(
+        repo.delete(ferris),
+        Err(RepositoryError::NotFound(_))
+    ));
+    assert!(matches!(
+        repo.update(ferris, user("ferris", "ferris@example.com")),
+        Err(RepositoryError::NotFound(_))
+    ));
+
+    // Ids are never reused.
+    let again = repo.create(user("ferris", "ferris@example.com")).unwrap();
+    assert_ne!(again, ferris);
+    assert_eq!(
+        repo.find_by_username(&username("nobody"))
+            .unwrap_err()
+            .to_string(),
+        "no user with username 'nobody'"
+    );
+    assert_eq!(UserId(3).to_string(), "#3");
+}
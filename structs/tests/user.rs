@@ -0,0 +1,76 @@
+use structs::user::{Email, User, UserError, Username, MAX_USERNAME_LEN};
+
+#[test]
+fn valid_details_build_an_active_user() {
+    let user = User::parse("ferris_42", "Ferris@Example.com").unwrap();
+    assert!(user.active());
+    assert_eq!(user.username().as_str(), "ferris_42");
+    assert_eq!(user.email().to_string(), "Ferris@Example.com");
+    assert_eq!(user.sign_in_count(), 1);
+}
+
+#[test]
+fn usernames_are_validated() {
+    assert_eq!(Username::new(""), Err(UserError::EmptyUsername));
+    let long = "a".repeat(MAX_USERNAME_LEN + 1);
+    assert_eq!(
+        Username::new(&long),
+        Err(UserError::UsernameTooLong {
+            len: MAX_USERNAME_LEN + 1
+        })
+    );
+    assert!(Username::new(&"a".repeat(MAX_USERNAME_LEN)).is_ok());
+    assert_eq!(
+        Username::new("two words"),
+        Err(UserError::InvalidUsernameChar(' '))
+    );
+    assert_eq!(
+        Username::new("Root"),
+        Err(UserError::ReservedUsername("Root".to_string()))
+    );
+    assert!(Username::new("rooted").is_ok());
+    assert!(Username::new("jean-luc.o_neil").is_ok());
+}
+
+#[test]
+fn malformed_emails_are_refused() {
+    for bad in [
+        "",
+        "plain",
+        "@example.com",
+        "someone@",
+        "someone@localhost",
+        "someone@example..com",
+        "some one@example.com",
+        "a@b@example.com",
+    ] {
+        assert_eq!(
+            Email::new(bad),
+            Err(UserError::InvalidEmail(bad.to_string())),
+            "{bad:?}"
+        );
+    }
+    assert!(Email::new("first.last+tag@mail.example.org").is_ok());
+}
+
+#[test]
+fn swapped_arguments_are_caught() {
+    assert_eq!(
+        User::parse("someone@example.com", "someusername123"),
+        Err(UserError::InvalidUsernameChar('@'))
+    );
+}
+
+#[test]
+fn changes_go_through_methods() {
+    let mut user = User::new(
+        "ferris".parse().unwrap(),
+        "ferris@example.com".parse().unwrap(),
+    );
+    user.sign_in();
+    user.set_email(Email::new("crab@example.com").unwrap());
+    user.deactivate();
+    assert_eq!(user.sign_in_count(), 2);
+    assert_eq!(user.email().as_str(), "crab@example.com");
+    assert!(!user.active());
+}